@@ -0,0 +1,301 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Cached installation token, kept in the state directory
+const TOKEN_CACHE_FILE: &str = "app-token.json";
+
+/// Mint a new installation token when the cached one expires sooner than this
+const REFRESH_MARGIN_MINUTES: i64 = 5;
+
+/// Credentials set via the --app-* flags; take precedence over DONER_APP_* variables
+static CREDENTIALS: OnceLock<AppCredentials> = OnceLock::new();
+
+/// GitHub App installation used to mint access tokens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppCredentials {
+    pub app_id: String,
+    pub private_key_path: PathBuf,
+    pub installation_id: String,
+}
+
+/// Authenticate as a GitHub App installation instead of a personal token
+pub fn set_credentials(credentials: AppCredentials) {
+    let _ = CREDENTIALS.set(credentials);
+}
+
+/// Get the configured App credentials from the --app-* flags or DONER_APP_* variables.
+/// Returns None when no App is configured, and an error when only some values are set.
+pub fn credentials() -> Result<Option<AppCredentials>> {
+    if let Some(credentials) = CREDENTIALS.get() {
+        return Ok(Some(credentials.clone()));
+    }
+
+    let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    let app_id = var("DONER_APP_ID");
+    let key_path = var("DONER_APP_PRIVATE_KEY");
+    let installation_id = var("DONER_APP_INSTALLATION_ID");
+
+    match (app_id, key_path, installation_id) {
+        (None, None, None) => Ok(None),
+        (Some(app_id), Some(key_path), Some(installation_id)) => Ok(Some(AppCredentials {
+            app_id,
+            private_key_path: PathBuf::from(key_path),
+            installation_id,
+        })),
+        _ => Err(anyhow!(
+            "Incomplete GitHub App configuration: set DONER_APP_ID, DONER_APP_PRIVATE_KEY \
+             and DONER_APP_INSTALLATION_ID together"
+        )),
+    }
+}
+
+#[derive(Serialize)]
+struct Claims<'a> {
+    iat: i64,
+    exp: i64,
+    iss: &'a str,
+}
+
+/// Sign the short-lived JWT GitHub expects when authenticating as the App itself
+fn app_jwt(app_id: &str, private_key: &[u8], now: DateTime<Utc>) -> Result<String> {
+    let key = EncodingKey::from_rsa_pem(private_key).map_err(|e| anyhow!("Invalid GitHub App private key: {}", e))?;
+    // Backdate to tolerate clock drift; GitHub rejects expirations over 10 minutes out
+    let claims = Claims {
+        iat: (now - Duration::seconds(60)).timestamp(),
+        exp: (now + Duration::minutes(9)).timestamp(),
+        iss: app_id,
+    };
+    jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &key)
+        .map_err(|e| anyhow!("Failed to sign GitHub App JWT: {}", e))
+}
+
+/// An installation access token and the App installation it belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedToken {
+    app_id: String,
+    installation_id: String,
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+impl CachedToken {
+    /// Usable for this installation and not about to expire
+    fn is_valid_for(&self, credentials: &AppCredentials, now: DateTime<Utc>) -> bool {
+        self.app_id == credentials.app_id
+            && self.installation_id == credentials.installation_id
+            && self.expires_at - Duration::minutes(REFRESH_MARGIN_MINUTES) > now
+    }
+}
+
+/// Request a new installation access token from GitHub
+async fn mint_token(credentials: &AppCredentials, api_url: &str) -> Result<CachedToken> {
+    let private_key = std::fs::read(&credentials.private_key_path).with_context(|| {
+        format!(
+            "Failed to read GitHub App private key {}",
+            credentials.private_key_path.display()
+        )
+    })?;
+    let jwt = app_jwt(&credentials.app_id, &private_key, Utc::now())?;
+
+    let response = crate::http::client()?
+        .post(format!(
+            "{}/app/installations/{}/access_tokens",
+            api_url.trim_end_matches('/'),
+            credentials.installation_id
+        ))
+        .header("Authorization", format!("Bearer {}", jwt))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "doner-cli")
+        .send()
+        .await
+        .map_err(|e| crate::http::request_error(e, "Failed to connect to GitHub API"))?;
+
+    let status = response.status();
+    let body = crate::http::redact(&response.text().await.unwrap_or_default(), &jwt);
+    if !status.is_success() {
+        return Err(anyhow!(
+            "Failed to create GitHub App installation token (HTTP {}): {}",
+            status,
+            body
+        ));
+    }
+
+    #[derive(Deserialize)]
+    struct Response {
+        token: String,
+        expires_at: DateTime<Utc>,
+    }
+
+    let response: Response =
+        serde_json::from_str(&body).context("Failed to parse GitHub App installation token response")?;
+    Ok(CachedToken {
+        app_id: credentials.app_id.clone(),
+        installation_id: credentials.installation_id.clone(),
+        token: response.token,
+        expires_at: response.expires_at,
+    })
+}
+
+fn cache_path() -> Result<PathBuf> {
+    Ok(crate::state::state_dir()?.join(TOKEN_CACHE_FILE))
+}
+
+fn load_cached_token() -> Option<CachedToken> {
+    let contents = std::fs::read_to_string(cache_path().ok()?).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn save_cached_token(token: &CachedToken) -> Result<()> {
+    let path = cache_path()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create state directory {}", dir.display()))?;
+    }
+    crate::auth::write_private_file(&path, serde_json::to_string_pretty(token)?.as_bytes())
+        .with_context(|| format!("Failed to write token cache {}", path.display()))
+}
+
+/// Get an installation access token, reusing the cached one until it nears expiry
+pub async fn installation_token(credentials: &AppCredentials) -> Result<String> {
+    if let Some(cached) = load_cached_token()
+        && cached.is_valid_for(credentials, Utc::now())
+    {
+        return Ok(cached.token);
+    }
+
+    let token = mint_token(credentials, &crate::http::api_url()?).await?;
+    if let Err(e) = save_cached_token(&token) {
+        eprintln!("Warning: could not cache GitHub App token: {}", e);
+    }
+    Ok(token.token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{DecodingKey, Validation};
+    use wiremock::matchers::{header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const TEST_KEY: &str = include_str!("testdata/app_key.pem");
+
+    fn credentials(key_path: PathBuf) -> AppCredentials {
+        AppCredentials {
+            app_id: "12345".to_string(),
+            private_key_path: key_path,
+            installation_id: "678".to_string(),
+        }
+    }
+
+    fn key_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("doner-{}-{}.pem", name, std::process::id()));
+        std::fs::write(&path, TEST_KEY).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_app_jwt_claims() {
+        let now = Utc::now();
+        let jwt = app_jwt("12345", TEST_KEY.as_bytes(), now).unwrap();
+
+        let header = jsonwebtoken::decode_header(&jwt).unwrap();
+        assert_eq!(header.alg, Algorithm::RS256);
+
+        #[derive(Deserialize)]
+        struct Decoded {
+            iat: i64,
+            exp: i64,
+            iss: String,
+        }
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.insecure_disable_signature_validation();
+        validation.validate_exp = false;
+        let claims = jsonwebtoken::decode::<Decoded>(&jwt, &DecodingKey::from_secret(&[]), &validation)
+            .unwrap()
+            .claims;
+        assert_eq!(claims.iss, "12345");
+        assert_eq!(claims.iat, now.timestamp() - 60);
+        assert!(claims.exp - now.timestamp() <= 600);
+    }
+
+    #[test]
+    fn test_app_jwt_rejects_invalid_key() {
+        let err = app_jwt("12345", b"not a key", Utc::now()).unwrap_err();
+        assert!(err.to_string().contains("Invalid GitHub App private key"));
+    }
+
+    #[test]
+    fn test_cached_token_validity() {
+        let now = Utc::now();
+        let creds = credentials(PathBuf::from("key.pem"));
+        let token = |expires_at| CachedToken {
+            app_id: "12345".to_string(),
+            installation_id: "678".to_string(),
+            token: "ghs_cached".to_string(),
+            expires_at,
+        };
+
+        assert!(token(now + Duration::minutes(30)).is_valid_for(&creds, now));
+        // Refreshed shortly before expiry so in-flight requests don't fail
+        assert!(!token(now + Duration::minutes(4)).is_valid_for(&creds, now));
+        assert!(!token(now - Duration::minutes(1)).is_valid_for(&creds, now));
+
+        let other = AppCredentials {
+            installation_id: "999".to_string(),
+            ..creds
+        };
+        assert!(!token(now + Duration::minutes(30)).is_valid_for(&other, now));
+    }
+
+    #[tokio::test]
+    async fn test_mint_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/app/installations/678/access_tokens"))
+            .and(header_exists("Authorization"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "token": "ghs_installation",
+                "expires_at": "2030-01-01T00:00:00Z"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let key = key_file("mint");
+        let token = mint_token(&credentials(key.clone()), &server.uri()).await.unwrap();
+        let _ = std::fs::remove_file(key);
+
+        assert_eq!(token.token, "ghs_installation");
+        assert_eq!(token.installation_id, "678");
+        assert_eq!(token.expires_at.to_rfc3339(), "2030-01-01T00:00:00+00:00");
+    }
+
+    #[tokio::test]
+    async fn test_mint_token_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/app/installations/678/access_tokens"))
+            .respond_with(ResponseTemplate::new(404).set_body_string(r#"{"message":"Not Found"}"#))
+            .mount(&server)
+            .await;
+
+        let key = key_file("mint-error");
+        let err = mint_token(&credentials(key.clone()), &server.uri()).await.unwrap_err();
+        let _ = std::fs::remove_file(key);
+
+        assert!(err.to_string().contains("HTTP 404"));
+        assert!(err.to_string().contains("Not Found"));
+    }
+
+    #[tokio::test]
+    async fn test_mint_token_missing_key() {
+        let err = mint_token(&credentials(PathBuf::from("/nonexistent/key.pem")), "http://127.0.0.1:1")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Failed to read GitHub App private key"));
+    }
+}
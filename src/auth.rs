@@ -1,20 +1,65 @@
+use age::secrecy::SecretString;
 use anyhow::{anyhow, Context, Result};
 use keyring::Entry;
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
 
 const SERVICE_NAME: &str = "doner-cli";
 const USERNAME: &str = "github-token";
 
-/// Get the keyring entry for the GitHub token
+/// File used when the OS keychain is unavailable (e.g. headless Linux without Secret Service)
+const CREDENTIALS_FILE: &str = "credentials";
+
+/// Host whose token is in use, from --api-url or DONER_API_URL
+pub fn active_host() -> Result<String> {
+    Ok(crate::http::api_host(&crate::http::api_url()?))
+}
+
+/// Storage name for a host's token; github.com keeps the original unsuffixed name
+fn host_key(base: &str, host: &str, separator: char) -> String {
+    if host == "github.com" {
+        base.to_string()
+    } else {
+        format!("{}{}{}", base, separator, host)
+    }
+}
+
+/// Where a stored token lives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenStore {
+    Keychain,
+    EncryptedFile,
+}
+
+impl TokenStore {
+    pub fn name(self) -> &'static str {
+        match self {
+            TokenStore::Keychain => "keychain",
+            TokenStore::EncryptedFile => "encrypted file",
+        }
+    }
+}
+
+/// Get the keyring entry for the active host's GitHub token
 fn get_entry() -> Result<Entry> {
-    Entry::new(SERVICE_NAME, USERNAME)
+    Entry::new(SERVICE_NAME, &host_key(USERNAME, &active_host()?, '@'))
         .map_err(|e| anyhow!("Failed to create keyring entry: {} (kind: {:?})", e, e))
 }
 
-/// Store a GitHub token in the system keychain
-pub fn store_token(token: &str) -> Result<()> {
-    let entry = get_entry()?;
+/// Check whether a keyring error means no keychain backend is usable
+fn keychain_unavailable(e: &keyring::Error) -> bool {
+    matches!(e, keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_))
+}
+
+/// Store a GitHub token, preferring the system keychain over the encrypted file
+pub fn store_token(token: &str) -> Result<TokenStore> {
+    let entry = match get_entry() {
+        Ok(entry) => entry,
+        Err(_) => return store_token_file(token).map(|_| TokenStore::EncryptedFile),
+    };
     match entry.set_password(token) {
-        Ok(()) => Ok(()),
+        Ok(()) => Ok(TokenStore::Keychain),
+        Err(e) if keychain_unavailable(&e) => store_token_file(token).map(|_| TokenStore::EncryptedFile),
         Err(e) => Err(anyhow!(
             "Failed to store token in keychain: {} (debug: {:?})",
             e,
@@ -23,52 +68,179 @@ pub fn store_token(token: &str) -> Result<()> {
     }
 }
 
-/// Retrieve the stored GitHub token from the system keychain
+/// Retrieve the stored GitHub token from the system keychain or the encrypted file
 pub fn get_token() -> Result<String> {
-    let entry = get_entry()?;
-    entry
-        .get_password()
-        .map_err(|e| anyhow!("Failed to retrieve token from keychain: {}", e))
+    let keychain = get_entry().and_then(|entry| {
+        entry
+            .get_password()
+            .map_err(|e| anyhow!("Failed to retrieve token from keychain: {}", e))
+    });
+
+    match keychain {
+        Ok(token) => Ok(token),
+        Err(_) if credentials_path()?.exists() => get_token_file(),
+        Err(e) => Err(e),
+    }
 }
 
-/// Delete the stored GitHub token from the system keychain
+/// Delete the stored GitHub token from both the system keychain and the encrypted file
 pub fn delete_token() -> Result<()> {
-    let entry = get_entry()?;
-    match entry.delete_credential() {
-        Ok(()) => Ok(()),
-        Err(keyring::Error::NoEntry) => Ok(()), // Already deleted, that's fine
-        Err(e) => Err(anyhow!("Failed to delete token from keychain: {}", e)),
+    if let Ok(entry) = get_entry() {
+        match entry.delete_credential() {
+            Ok(()) => {}
+            Err(keyring::Error::NoEntry) => {} // Already deleted, that's fine
+            Err(e) if keychain_unavailable(&e) => {}
+            Err(e) => return Err(anyhow!("Failed to delete token from keychain: {}", e)),
+        }
+    }
+
+    let path = credentials_path()?;
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove credentials file {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Find where a token is stored, without decrypting the file
+pub fn token_store() -> Option<TokenStore> {
+    if get_entry().is_ok_and(|entry| entry.get_password().is_ok()) {
+        return Some(TokenStore::Keychain);
     }
+    credentials_path()
+        .is_ok_and(|path| path.exists())
+        .then_some(TokenStore::EncryptedFile)
 }
 
 /// Check if a token is stored
 pub fn has_token() -> bool {
-    get_token().is_ok()
+    token_store().is_some()
 }
 
-/// Get a token from environment variable or keychain
-/// Priority: GITHUB_TOKEN env var > stored token
-pub fn resolve_token() -> Result<String> {
-    // First try environment variable
+/// Get the config directory
+/// Priority: $XDG_CONFIG_HOME/doner > ~/.config/doner
+fn config_dir() -> Result<PathBuf> {
+    if let Ok(dir) = std::env::var("XDG_CONFIG_HOME")
+        && !dir.is_empty()
+    {
+        return Ok(PathBuf::from(dir).join("doner"));
+    }
+
+    let home = std::env::var("HOME").map_err(|_| anyhow!("Could not determine home directory"))?;
+    Ok(PathBuf::from(home).join(".config").join("doner"))
+}
+
+fn credentials_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join(host_key(CREDENTIALS_FILE, &active_host()?, '-')))
+}
+
+/// Passphrase for the encrypted file, from DONER_KEYSTORE_PASS or an interactive prompt
+fn keystore_passphrase() -> Result<SecretString> {
+    if let Ok(pass) = std::env::var("DONER_KEYSTORE_PASS")
+        && !pass.is_empty()
+    {
+        return Ok(SecretString::from(pass));
+    }
+
+    let pass = rpassword::prompt_password("Passphrase for the doner credentials file: ")
+        .context("Failed to read passphrase (set DONER_KEYSTORE_PASS for non-interactive use)")?;
+    if pass.is_empty() {
+        return Err(anyhow!("Passphrase cannot be empty"));
+    }
+    Ok(SecretString::from(pass))
+}
+
+fn store_token_file(token: &str) -> Result<()> {
+    eprintln!("System keychain unavailable, storing the token in an encrypted file instead.");
+    let path = credentials_path()?;
+    let encrypted = encrypt_token(token, keystore_passphrase()?, None)?;
+
+    let dir = config_dir()?;
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create config directory {}", dir.display()))?;
+    write_private_file(&path, encrypted.as_bytes())
+        .with_context(|| format!("Failed to write credentials file {}", path.display()))
+}
+
+/// Write a file only the current user can read. On Unix it is created with mode 0600 (and an
+/// existing file is narrowed to it) before anything is written, so the contents are never exposed
+pub(crate) fn write_private_file(path: &std::path::Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents)
+}
+
+fn get_token_file() -> Result<String> {
+    let path = credentials_path()?;
+    let encrypted = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read credentials file {}", path.display()))?;
+    decrypt_token(&encrypted, keystore_passphrase()?)
+}
+
+/// Encrypt a token with an age passphrase, as ASCII-armored text
+fn encrypt_token(token: &str, passphrase: SecretString, work_factor: Option<u8>) -> Result<String> {
+    let mut recipient = age::scrypt::Recipient::new(passphrase);
+    if let Some(log_n) = work_factor {
+        recipient.set_work_factor(log_n);
+    }
+    age::encrypt_and_armor(&recipient, token.as_bytes()).map_err(|e| anyhow!("Failed to encrypt token: {}", e))
+}
+
+fn decrypt_token(encrypted: &str, passphrase: SecretString) -> Result<String> {
+    let identity = age::scrypt::Identity::new(passphrase);
+    let token = age::decrypt(&identity, encrypted.as_bytes())
+        .map_err(|e| anyhow!("Failed to decrypt credentials file (wrong passphrase?): {}", e))?;
+    String::from_utf8(token).context("Credentials file does not contain a valid token")
+}
+
+/// Get a token from a GitHub App installation, environment variable or keychain
+/// Priority: GitHub App credentials > GITHUB_TOKEN env var > stored token
+pub async fn resolve_token() -> Result<String> {
+    if let Some(credentials) = crate::app_auth::credentials()? {
+        return crate::app_auth::installation_token(&credentials).await;
+    }
+
+    // Then try environment variable
     if let Ok(token) = std::env::var("GITHUB_TOKEN") {
         return Ok(token);
     }
 
-    // Then try keychain
+    // Then try the keychain or encrypted file of the active host
+    let host = active_host()?;
     get_token().map_err(|_| {
+        let login = if host == "github.com" {
+            "doner auth login".to_string()
+        } else {
+            format!("doner auth login --api-url {}", host)
+        };
         anyhow!(
-            "No GitHub token found. Either:\n  \
-             1. Run 'doner auth login' to authenticate\n  \
-             2. Set the GITHUB_TOKEN environment variable"
+            "No GitHub token found for {}. Either:\n  \
+             1. Run '{}' to authenticate\n  \
+             2. Set the GITHUB_TOKEN environment variable",
+            host,
+            login
         )
     })
 }
 
-/// Interactive login - prompts for token
+/// Interactive login - prompts for token (on stderr, so it can run mid-report)
 pub fn interactive_login() -> Result<String> {
-    println!("Paste your GitHub personal access token:");
-    println!("(Create one at https://github.com/settings/tokens with 'read:project' and 'repo' scopes)");
-    println!();
+    eprintln!("Paste your GitHub personal access token:");
+    eprintln!("(Create one at https://github.com/settings/tokens with 'read:project' and 'repo' scopes)");
+    eprintln!();
 
     let token = rpassword::read_password().context("Failed to read token")?;
 
@@ -81,12 +253,56 @@ pub fn interactive_login() -> Result<String> {
     Ok(token)
 }
 
+/// Hex SHA-256 of a token, used to recognize it without storing it
+fn token_sha256(token: &str) -> String {
+    Sha256::digest(token.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Remember the login a token was validated as, keyed by the active host
+pub fn cache_login(token: &str, login: &str) -> Result<()> {
+    let mut state = crate::state::load()?;
+    state.logins.insert(
+        active_host()?,
+        crate::state::CachedLogin {
+            token_sha256: token_sha256(token),
+            login: login.to_string(),
+        },
+    );
+    crate::state::save(&state)
+}
+
+/// Forget the cached login for the active host
+pub fn clear_cached_login() -> Result<()> {
+    let mut state = crate::state::load()?;
+    if state.logins.remove(&active_host()?).is_some() {
+        crate::state::save(&state)?;
+    }
+    Ok(())
+}
+
+/// Login for a token, from the cache when the token is unchanged unless `refresh` is set.
+/// Returns the login and whether it came from the cache.
+pub async fn login_for_token(token: &str, refresh: bool) -> Result<(String, bool)> {
+    if !refresh
+        && let Ok(state) = crate::state::load()
+        && let Some(login) = state.cached_login(&active_host()?, &token_sha256(token))
+    {
+        return Ok((login.to_string(), true));
+    }
+
+    let login = validate_token(token).await?;
+    if let Err(e) = cache_login(token, &login) {
+        eprintln!("Warning: could not cache login: {}", e);
+    }
+    Ok((login, false))
+}
+
 /// Validate a token by making a test API call
 pub async fn validate_token(token: &str) -> Result<String> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client()?;
 
     let response = client
-        .post("https://api.github.com/graphql")
+        .post(crate::http::graphql_url(&crate::http::api_url()?))
         .header("Authorization", format!("Bearer {}", token))
         .header("User-Agent", "doner-cli")
         .json(&serde_json::json!({
@@ -94,7 +310,16 @@ pub async fn validate_token(token: &str) -> Result<String> {
         }))
         .send()
         .await
-        .context("Failed to connect to GitHub API")?;
+        .map_err(|e| {
+            let error = crate::http::request_error(e, "Failed to connect to GitHub API");
+            anyhow!(crate::http::redact(&format!("{:#}", error), token))
+        })?;
+
+    // Fine-grained tokens may authenticate but lack access to the viewer query
+    if response.status() == reqwest::StatusCode::FORBIDDEN {
+        let detail = crate::http::redact(&response.text().await.unwrap_or_default(), token);
+        return Err(crate::github::ProjectAccessError { owner: None, detail }.into());
+    }
 
     if !response.status().is_success() {
         return Err(anyhow!(
@@ -106,6 +331,7 @@ pub async fn validate_token(token: &str) -> Result<String> {
     #[derive(serde::Deserialize)]
     struct Response {
         data: Option<Data>,
+        errors: Option<Vec<crate::models::GraphQLError>>,
     }
 
     #[derive(serde::Deserialize)]
@@ -120,6 +346,12 @@ pub async fn validate_token(token: &str) -> Result<String> {
 
     let body: Response = response.json().await.context("Failed to parse response")?;
 
+    if let Some(errors) = &body.errors
+        && crate::github::is_access_error(errors)
+    {
+        return Err(crate::github::graphql_error(errors, None));
+    }
+
     let username = body
         .data
         .map(|d| d.viewer.login)
@@ -127,3 +359,59 @@ pub async fn validate_token(token: &str) -> Result<String> {
 
     Ok(username)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_private_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("doner-private-{}", std::process::id()));
+        std::fs::write(&path, "old contents that are longer").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private_file(&path, b"secret").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "secret");
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_encrypted_token_roundtrip() {
+        let encrypted = encrypt_token("ghp_secret", SecretString::from("hunter2".to_string()), Some(2)).unwrap();
+        assert!(encrypted.starts_with("-----BEGIN AGE ENCRYPTED FILE-----"));
+        assert!(!encrypted.contains("ghp_secret"));
+
+        let token = decrypt_token(&encrypted, SecretString::from("hunter2".to_string())).unwrap();
+        assert_eq!(token, "ghp_secret");
+    }
+
+    #[test]
+    fn test_token_sha256() {
+        let hash = token_sha256("ghp_secret");
+        assert_eq!(hash.len(), 64);
+        assert!(!hash.contains("ghp_secret"));
+        assert_eq!(hash, token_sha256("ghp_secret"));
+        assert_ne!(hash, token_sha256("ghp_other"));
+        assert_eq!(
+            token_sha256(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_host_key() {
+        assert_eq!(host_key(USERNAME, "github.com", '@'), "github-token");
+        assert_eq!(host_key(USERNAME, "ghe.corp.com", '@'), "github-token@ghe.corp.com");
+        assert_eq!(host_key(CREDENTIALS_FILE, "ghe.corp.com", '-'), "credentials-ghe.corp.com");
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let encrypted = encrypt_token("ghp_secret", SecretString::from("hunter2".to_string()), Some(2)).unwrap();
+        assert!(decrypt_token(&encrypted, SecretString::from("nope".to_string())).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use clap::ValueEnum;
+use std::io::IsTerminal;
+
+/// When to emit ANSI colors in terminal output
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and NO_COLOR is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Decide whether colors should be used for stdout
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => !no_color_env() && std::io::stdout().is_terminal(),
+        }
+    }
+
+    /// Decide whether OSC 8 hyperlinks may be emitted on stdout. NO_COLOR does not apply,
+    /// but `never` keeps the output free of any escape codes.
+    pub fn hyperlinks(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => std::io::stdout().is_terminal(),
+        }
+    }
+}
+
+/// Check the NO_COLOR convention (https://no-color.org): set and non-empty disables color
+fn no_color_env() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+/// Applies ANSI styles when enabled, otherwise passes text through unchanged
+#[derive(Debug, Clone, Copy)]
+pub struct Styler {
+    enabled: bool,
+    hyperlinks: bool,
+}
+
+impl Styler {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            hyperlinks: false,
+        }
+    }
+
+    /// Also wrap text passed to `link` in OSC 8 hyperlink escapes
+    pub fn with_hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.hyperlinks = hyperlinks;
+        self
+    }
+
+    /// Make `text` a clickable link to `url` in terminals that support OSC 8
+    pub fn link(&self, text: &str, url: &str) -> String {
+        if self.hyperlinks && !url.is_empty() {
+            format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    pub fn bold(&self, text: &str) -> String {
+        self.paint("1", text)
+    }
+
+    pub fn cyan(&self, text: &str) -> String {
+        self.paint("36", text)
+    }
+
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.enabled {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_styler_is_plain() {
+        let style = Styler::new(false);
+        assert_eq!(style.bold("title"), "title");
+        assert_eq!(style.cyan("#1"), "#1");
+    }
+
+    #[test]
+    fn test_enabled_styler_wraps_text() {
+        let style = Styler::new(true);
+        assert_eq!(style.bold("title"), "\x1b[1mtitle\x1b[0m");
+    }
+
+    #[test]
+    fn test_explicit_choices() {
+        assert!(ColorChoice::Always.enabled());
+        assert!(!ColorChoice::Never.enabled());
+        assert!(ColorChoice::Always.hyperlinks());
+        assert!(!ColorChoice::Never.hyperlinks());
+    }
+
+    #[test]
+    fn test_link() {
+        let url = "https://github.com/myorg/repo/issues/1";
+        assert_eq!(Styler::new(true).link("#1", url), "#1");
+        assert_eq!(
+            Styler::new(false).with_hyperlinks(true).link("#1", url),
+            "\x1b]8;;https://github.com/myorg/repo/issues/1\x1b\\#1\x1b]8;;\x1b\\"
+        );
+        // Draft items have no URL to point at
+        assert_eq!(Styler::new(false).with_hyperlinks(true).link("#1", ""), "#1");
+    }
+}
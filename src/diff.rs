@@ -0,0 +1,222 @@
+use std::collections::HashSet;
+
+use crate::models::Issue;
+use crate::output::repository_counts;
+
+/// Issues of two windows compared by URL (drafts, which have no URL, by node ID)
+#[derive(Debug, Default)]
+pub struct IssueDiff {
+    /// Only in the current window
+    pub added: Vec<Issue>,
+    /// Only in the previous window
+    pub removed: Vec<Issue>,
+    /// In both windows, as fetched for the current one
+    pub common: Vec<Issue>,
+    pub previous_count: usize,
+    pub current_count: usize,
+    previous_repos: Vec<(String, usize)>,
+    current_repos: Vec<(String, usize)>,
+}
+
+/// Change in a repository's issue count between the two windows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryDelta {
+    pub repository: String,
+    pub previous: usize,
+    pub current: usize,
+}
+
+fn key(issue: &Issue) -> &str {
+    if issue.is_draft { &issue.id } else { &issue.url }
+}
+
+fn owned_counts(issues: &[Issue]) -> Vec<(String, usize)> {
+    repository_counts(issues)
+        .into_iter()
+        .map(|(repo, count)| (repo.to_string(), count))
+        .collect()
+}
+
+/// Split the issues of two windows into added, removed, and common
+pub fn diff_issues(previous: Vec<Issue>, current: Vec<Issue>) -> IssueDiff {
+    let previous_keys: HashSet<&str> = previous.iter().map(key).collect();
+    let current_keys: HashSet<&str> = current.iter().map(key).collect();
+    let removed = previous.iter().filter(|i| !current_keys.contains(key(i))).cloned().collect();
+    let (common, added) = current.iter().cloned().partition(|i| previous_keys.contains(key(i)));
+
+    IssueDiff {
+        added,
+        removed,
+        common,
+        previous_count: previous.len(),
+        current_count: current.len(),
+        previous_repos: owned_counts(&previous),
+        current_repos: owned_counts(&current),
+    }
+}
+
+impl IssueDiff {
+    /// Repositories whose issue count changed, largest change first
+    pub fn repository_deltas(&self) -> Vec<RepositoryDelta> {
+        let count = |counts: &[(String, usize)], repo: &str| {
+            counts.iter().find(|(r, _)| r == repo).map_or(0, |(_, c)| *c)
+        };
+        let repos: HashSet<&str> = self
+            .previous_repos
+            .iter()
+            .chain(&self.current_repos)
+            .map(|(repo, _)| repo.as_str())
+            .collect();
+
+        let mut deltas: Vec<RepositoryDelta> = repos
+            .into_iter()
+            .map(|repo| RepositoryDelta {
+                repository: repo.to_string(),
+                previous: count(&self.previous_repos, repo),
+                current: count(&self.current_repos, repo),
+            })
+            .filter(|delta| delta.previous != delta.current)
+            .collect();
+        deltas.sort_by(|a, b| {
+            a.previous
+                .abs_diff(a.current)
+                .cmp(&b.previous.abs_diff(b.current))
+                .reverse()
+                .then_with(|| a.repository.cmp(&b.repository))
+        });
+        deltas
+    }
+}
+
+fn signed(previous: usize, current: usize) -> String {
+    format!("{:+}", current as i64 - previous as i64)
+}
+
+fn issue_ref(issue: &Issue) -> String {
+    if issue.is_draft {
+        "draft".to_string()
+    } else {
+        format!("{}#{}", issue.repository, issue.number)
+    }
+}
+
+/// Render the counts, repository changes, and added/removed/common issues as text or markdown
+pub fn format_diff(diff: &IssueDiff, previous_label: &str, current_label: &str, markdown: bool) -> String {
+    let mut output = String::new();
+    let headline = format!(
+        "{} → {}: {} → {} issues ({})",
+        previous_label,
+        current_label,
+        diff.previous_count,
+        diff.current_count,
+        signed(diff.previous_count, diff.current_count)
+    );
+    if markdown {
+        output.push_str(&format!("## {}\n\n", headline));
+    } else {
+        output.push_str(&format!("{}\n", headline));
+    }
+    output.push_str(&format!(
+        "Added: {}, removed: {}, in both: {}\n",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.common.len()
+    ));
+
+    let deltas = diff.repository_deltas();
+    if !deltas.is_empty() {
+        output.push_str(if markdown { "\n### Repositories\n\n" } else { "\nRepositories:\n" });
+        for delta in &deltas {
+            let line = format!(
+                "{}: {} → {} ({})",
+                delta.repository,
+                delta.previous,
+                delta.current,
+                signed(delta.previous, delta.current)
+            );
+            output.push_str(&format!("{}{}\n", if markdown { "- " } else { "  " }, line));
+        }
+    }
+
+    for (heading, issues) in [("Added", &diff.added), ("Removed", &diff.removed), ("In both", &diff.common)] {
+        if issues.is_empty() {
+            continue;
+        }
+        if markdown {
+            output.push_str(&format!("\n### {} ({})\n\n", heading, issues.len()));
+        } else {
+            output.push_str(&format!("\n{} ({}):\n", heading, issues.len()));
+        }
+        for issue in issues {
+            let line = match (markdown, issue.is_draft) {
+                (true, false) => format!("- [{}]({}) {}", issue_ref(issue), issue.url, issue.title),
+                (true, true) => format!("- {} ({})", issue.title, issue_ref(issue)),
+                (false, _) => format!("  • [{}] {}", issue_ref(issue), issue.title),
+            };
+            output.push_str(&format!("{}\n", line));
+        }
+    }
+
+    output.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(repo: &str, number: u64) -> Issue {
+        Issue {
+            number,
+            title: format!("Issue {}", number),
+            url: format!("https://github.com/{}/issues/{}", repo, number),
+            repository: repo.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_diff_issues() {
+        let previous = vec![issue("myorg/api", 1), issue("myorg/api", 2), issue("myorg/web", 3)];
+        let current = vec![issue("myorg/api", 2), issue("myorg/web", 4), issue("myorg/web", 5), issue("myorg/cli", 6)];
+        let diff = diff_issues(previous, current);
+
+        let numbers = |issues: &[Issue]| issues.iter().map(|i| i.number).collect::<Vec<_>>();
+        assert_eq!(numbers(&diff.added), vec![4, 5, 6]);
+        assert_eq!(numbers(&diff.removed), vec![1, 3]);
+        assert_eq!(numbers(&diff.common), vec![2]);
+        assert_eq!((diff.previous_count, diff.current_count), (3, 4));
+
+        let deltas: Vec<_> = diff
+            .repository_deltas()
+            .into_iter()
+            .map(|d| (d.repository, d.previous, d.current))
+            .collect();
+        assert_eq!(
+            deltas,
+            vec![("myorg/api".to_string(), 2, 1), ("myorg/cli".to_string(), 0, 1), ("myorg/web".to_string(), 1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_format_diff() {
+        let diff = diff_issues(vec![issue("myorg/api", 1)], vec![issue("myorg/api", 1), issue("myorg/api", 2)]);
+
+        let text = format_diff(&diff, "@previous", "@current", false);
+        assert!(text.starts_with("@previous → @current: 1 → 2 issues (+1)\nAdded: 1, removed: 0, in both: 1"));
+        assert!(text.contains("Repositories:\n  myorg/api: 1 → 2 (+1)"));
+        assert!(text.contains("Added (1):\n  • [myorg/api#2] Issue 2"));
+        assert!(!text.contains("Removed"));
+
+        let markdown = format_diff(&diff, "@previous", "@current", true);
+        assert!(markdown.starts_with("## @previous → @current: 1 → 2 issues (+1)"));
+        assert!(markdown.contains("### In both (1)\n\n- [myorg/api#1](https://github.com/myorg/api/issues/1) Issue 1"));
+    }
+
+    #[test]
+    fn test_format_diff_unchanged() {
+        let diff = diff_issues(vec![issue("myorg/api", 1)], vec![issue("myorg/api", 2)]);
+        let text = format_diff(&diff, "Sprint 1", "Sprint 2", false);
+        assert!(text.starts_with("Sprint 1 → Sprint 2: 1 → 1 issues (+0)"));
+        assert!(!text.contains("Repositories"));
+    }
+}
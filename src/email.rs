@@ -0,0 +1,181 @@
+use anyhow::{anyhow, Context, Result};
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+/// Submission port with STARTTLS, used when DONER_SMTP_PORT is unset
+const DEFAULT_SMTP_PORT: u16 = 587;
+
+/// Port conventionally used for implicit TLS
+const IMPLICIT_TLS_PORT: u16 = 465;
+
+/// How the connection to the SMTP server is secured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpTls {
+    /// Upgrade a plain connection with STARTTLS
+    StartTls,
+    /// Connect over TLS from the start
+    Implicit,
+    /// No encryption, e.g. for a local relay
+    None,
+}
+
+/// SMTP server settings, read from DONER_SMTP_* variables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub tls: SmtpTls,
+    pub user: Option<String>,
+    pub pass: Option<String>,
+    pub from: String,
+}
+
+impl SmtpConfig {
+    /// Read the SMTP settings; DONER_SMTP_HOST and DONER_SMTP_FROM are required
+    pub fn from_env() -> Result<Self> {
+        Self::from_vars(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let host = var("DONER_SMTP_HOST").ok_or_else(|| anyhow!("--email-to needs DONER_SMTP_HOST to be set"))?;
+        let from = var("DONER_SMTP_FROM").ok_or_else(|| anyhow!("--email-to needs DONER_SMTP_FROM to be set"))?;
+        let port = match var("DONER_SMTP_PORT") {
+            Some(port) => port
+                .trim()
+                .parse()
+                .map_err(|_| anyhow!("DONER_SMTP_PORT must be a port number, got '{}'", port))?,
+            None => DEFAULT_SMTP_PORT,
+        };
+        let tls = match var("DONER_SMTP_TLS").map(|v| v.trim().to_lowercase()).as_deref() {
+            None if port == IMPLICIT_TLS_PORT => SmtpTls::Implicit,
+            None | Some("starttls") => SmtpTls::StartTls,
+            Some("tls") => SmtpTls::Implicit,
+            Some("none") => SmtpTls::None,
+            Some(other) => {
+                return Err(anyhow!(
+                    "Invalid DONER_SMTP_TLS '{}': use starttls, tls, or none",
+                    other
+                ))
+            }
+        };
+
+        Ok(Self {
+            host,
+            port,
+            tls,
+            user: var("DONER_SMTP_USER"),
+            pass: var("DONER_SMTP_PASS"),
+            from,
+        })
+    }
+}
+
+/// Render markdown (the report or an AI summary) as an HTML document body
+pub fn markdown_to_html(markdown: &str) -> String {
+    let parser = pulldown_cmark::Parser::new_ext(markdown, pulldown_cmark::Options::ENABLE_TABLES);
+    let mut body = String::new();
+    pulldown_cmark::html::push_html(&mut body, parser);
+    format!("<!DOCTYPE html>\n<html>\n<body>\n{}</body>\n</html>\n", body)
+}
+
+/// Build a message with an HTML part and a plaintext fallback
+pub fn build_message(from: &str, to: &[String], subject: &str, plain: &str, html: &str) -> Result<Message> {
+    let from: Mailbox = from
+        .parse()
+        .map_err(|e| anyhow!("Invalid sender address '{}': {}", from, e))?;
+    let mut builder = Message::builder().from(from).subject(subject);
+    for address in to {
+        let mailbox: Mailbox = address
+            .parse()
+            .map_err(|e| anyhow!("Invalid recipient address '{}': {}", address, e))?;
+        builder = builder.to(mailbox);
+    }
+
+    builder
+        .multipart(MultiPart::alternative_plain_html(plain.to_string(), html.to_string()))
+        .context("Failed to build email")
+}
+
+/// Send the report to `to` through the configured SMTP server
+pub async fn send_report(config: &SmtpConfig, to: &[String], subject: &str, plain: &str, html: &str) -> Result<()> {
+    let message = build_message(&config.from, to, subject, plain, html)?;
+
+    let builder = match config.tls {
+        SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host),
+        SmtpTls::Implicit => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host),
+        SmtpTls::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)),
+    }
+    .with_context(|| format!("Invalid SMTP host '{}'", config.host))?;
+
+    let mut builder = builder.port(config.port);
+    if let Some(user) = &config.user {
+        builder = builder.credentials(Credentials::new(user.clone(), config.pass.clone().unwrap_or_default()));
+    }
+
+    builder
+        .build()
+        .send(message)
+        .await
+        .map_err(|e| anyhow!("Failed to send email via {}:{}: {}", config.host, config.port, e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(vars: &[(&str, &str)]) -> Result<SmtpConfig> {
+        let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        SmtpConfig::from_vars(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_smtp_config() {
+        let base = [("DONER_SMTP_HOST", "smtp.example.com"), ("DONER_SMTP_FROM", "doner@example.com")];
+        let config_default = config(&base).unwrap();
+        assert_eq!((config_default.port, config_default.tls), (587, SmtpTls::StartTls));
+        assert_eq!(config_default.user, None);
+
+        let implicit = config(&[base[0], base[1], ("DONER_SMTP_PORT", "465")]).unwrap();
+        assert_eq!(implicit.tls, SmtpTls::Implicit);
+
+        let local = config(&[base[0], base[1], ("DONER_SMTP_PORT", "25"), ("DONER_SMTP_TLS", "none")]).unwrap();
+        assert_eq!((local.port, local.tls), (25, SmtpTls::None));
+    }
+
+    #[test]
+    fn test_smtp_config_errors() {
+        assert!(config(&[]).unwrap_err().to_string().contains("DONER_SMTP_HOST"));
+        assert!(config(&[("DONER_SMTP_HOST", "smtp.example.com")]).unwrap_err().to_string().contains("DONER_SMTP_FROM"));
+        let bad_port = [("DONER_SMTP_HOST", "h"), ("DONER_SMTP_FROM", "a@b.c"), ("DONER_SMTP_PORT", "smtp")];
+        assert!(config(&bad_port).is_err());
+        let bad_tls = [("DONER_SMTP_HOST", "h"), ("DONER_SMTP_FROM", "a@b.c"), ("DONER_SMTP_TLS", "maybe")];
+        assert!(config(&bad_tls).is_err());
+    }
+
+    #[test]
+    fn test_markdown_to_html() {
+        let html = markdown_to_html("## Summary (1 issues)\n\n- **[myorg/repo#1](https://github.com/myorg/repo/issues/1)**: Fix");
+        assert!(html.contains("<h2>Summary (1 issues)</h2>"));
+        assert!(html.contains("<a href=\"https://github.com/myorg/repo/issues/1\">myorg/repo#1</a>"));
+    }
+
+    #[test]
+    fn test_build_message() {
+        let to = vec!["team@example.com".to_string()];
+        let message = build_message("Doner <doner@example.com>", &to, "Weekly digest", "plain body", "<p>html body</p>")
+            .unwrap();
+        let raw = String::from_utf8(message.formatted()).unwrap();
+        assert!(raw.contains("Subject: Weekly digest"));
+        assert!(raw.contains("To: team@example.com"));
+        assert!(raw.contains("multipart/alternative"));
+        assert!(raw.contains("plain body"));
+        assert!(raw.contains("<p>html body</p>"));
+
+        assert!(build_message("not an address", &to, "s", "p", "h").is_err());
+        let bad_to = vec!["nobody".to_string()];
+        assert!(build_message("doner@example.com", &bad_to, "s", "p", "h").is_err());
+    }
+}
@@ -1,137 +1,731 @@
 use anyhow::{anyhow, Context, Result};
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use regex::Regex;
 use reqwest::Client;
 use serde::Deserialize;
 use serde_json::json;
-use std::collections::HashSet;
-
+use futures_util::stream::{self, Stream, TryStreamExt};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use crate::iteration_filter::matches_iteration_filter;
 use crate::models::*;
 
-const GITHUB_GRAPHQL_URL: &str = "https://api.github.com/graphql";
-
-/// Check if an item's iteration matches the filter.
-/// Supported filter formats:
-/// - `@all` - matches all iterations (no filtering)
-/// - `@current` - matches the iteration that contains today's date
-/// - `@previous` - matches the iteration before current
-/// - `@current,@previous` - matches either current or previous
-/// - `<iteration name>` - exact match on iteration title
-fn matches_iteration_filter(
-    iteration_title: Option<&str>,
-    iteration_start: Option<&str>,
-    filter: &str,
-) -> bool {
-    // @all means no filtering
-    if filter == "@all" {
-        return true;
+/// Name of the single-select field holding the board column
+/// (overridable via DONER_STATUS_FIELD)
+pub fn status_field_name() -> String {
+    std::env::var("DONER_STATUS_FIELD").unwrap_or_else(|_| "Status".to_string())
+}
+
+/// A token that authenticates but is not allowed to read the project.
+/// Typical for fine-grained tokens created without the "Projects" permission.
+#[derive(Debug)]
+pub struct ProjectAccessError {
+    /// Project owner, when known
+    pub owner: Option<String>,
+    /// Message reported by GitHub
+    pub detail: String,
+}
+
+impl std::fmt::Display for ProjectAccessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let owner = self
+            .owner
+            .as_deref()
+            .map(|o| format!("'{}'", o))
+            .unwrap_or_else(|| "the project owner".to_string());
+        write!(
+            f,
+            "GitHub denied access to the project: {}\n  \
+             Fine-grained tokens need the \"Projects: read\" permission on {} \
+             (classic tokens need the 'read:project' scope). Update the token and run 'doner auth login' again.",
+            self.detail, owner
+        )
+    }
+}
+
+impl std::error::Error for ProjectAccessError {}
+
+/// GitHub rejected the token (HTTP 401), typically because it expired or was revoked
+#[derive(Debug)]
+pub struct AuthExpired {
+    /// Message reported by GitHub
+    pub detail: String,
+}
+
+impl std::fmt::Display for AuthExpired {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "GitHub rejected the token (HTTP 401): {}\n  \
+             The token may have expired or been revoked. Run 'doner auth login' to re-authenticate.",
+            self.detail
+        )
+    }
+}
+
+impl std::error::Error for AuthExpired {}
+
+/// Called when GitHub rejects the token mid-run; returns a replacement token,
+/// or None to give up with `AuthExpired`
+pub type ReauthHook = Box<dyn Fn() -> Result<Option<String>> + Send + Sync>;
+
+/// Check whether GraphQL errors indicate missing permissions rather than a missing resource
+pub fn is_access_error(errors: &[GraphQLError]) -> bool {
+    errors.iter().any(|e| {
+        matches!(e.error_type.as_deref(), Some("FORBIDDEN" | "INSUFFICIENT_SCOPES"))
+            || e.message.contains("not accessible by")
+    })
+}
+
+/// Turn GraphQL errors into an error, calling out permission problems explicitly
+pub fn graphql_error(errors: &[GraphQLError], owner: Option<&str>) -> anyhow::Error {
+    let messages: Vec<_> = errors.iter().map(|e| e.message.as_str()).collect();
+    if is_access_error(errors) {
+        return ProjectAccessError {
+            owner: owner.map(str::to_string),
+            detail: messages.join(", "),
+        }
+        .into();
+    }
+    anyhow!("GraphQL errors: {}", messages.join(", "))
+}
+
+/// A project node ID that can't be valid, caught before any request is made
+#[derive(Debug)]
+pub struct InvalidNodeId {
+    pub node_id: String,
+    /// What is wrong with it
+    pub reason: &'static str,
+}
+
+impl std::fmt::Display for InvalidNodeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid project node ID '{}': {}\n  \
+             Node IDs look like 'PVT_kwDOABCD1234'. Copy it again, or use 'owner/number' (e.g., 'myorg/5').",
+            self.node_id, self.reason
+        )
+    }
+}
+
+impl std::error::Error for InvalidNodeId {}
+
+/// Characters of the base64url-like payload after `PVT_`
+const NODE_ID_PAYLOAD: std::ops::RangeInclusive<usize> = 8..=64;
+
+/// Check that a `PVT_` node ID is plausibly well-formed, to catch copy-paste damage early
+pub fn validate_node_id(node_id: &str) -> std::result::Result<(), InvalidNodeId> {
+    let invalid = |reason| InvalidNodeId {
+        node_id: node_id.to_string(),
+        reason,
+    };
+    let payload = node_id.strip_prefix("PVT_").ok_or_else(|| invalid("it doesn't start with 'PVT_'"))?;
+    if !payload
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(invalid("it contains characters that never appear in node IDs"));
     }
+    if payload.len() < *NODE_ID_PAYLOAD.start() {
+        return Err(invalid("it is too short, so it may have been truncated"));
+    }
+    if payload.len() > *NODE_ID_PAYLOAD.end() {
+        return Err(invalid("it is too long, so something may have been pasted along with it"));
+    }
+    Ok(())
+}
+
+/// GitHub has no project with this node ID on the configured host
+#[derive(Debug)]
+pub struct ProjectNotFound {
+    pub node_id: String,
+    /// Host the lookup went to, e.g. github.com
+    pub host: String,
+}
+
+impl std::fmt::Display for ProjectNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Project '{}' not found on {}.\n  \
+             The node ID may be mistyped, or belong to a project on a different GitHub host (see --api-url).",
+            self.node_id, self.host
+        )
+    }
+}
+
+impl std::error::Error for ProjectNotFound {}
+
+/// A page request selected more nodes than GitHub allows in one query (500,000)
+#[derive(Debug)]
+struct NodeLimitError {
+    detail: String,
+}
+
+impl std::fmt::Display for NodeLimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GraphQL node limit exceeded: {}", self.detail)
+    }
+}
+
+impl std::error::Error for NodeLimitError {}
+
+/// Check whether GraphQL errors reject the query for selecting too many nodes
+fn is_node_limit_error(errors: &[GraphQLError]) -> bool {
+    errors
+        .iter()
+        .any(|e| e.error_type.as_deref() == Some("MAX_NODE_LIMIT_EXCEEDED"))
+}
+
+/// Minimum wait after hitting a secondary rate limit, per GitHub's guidance
+const SECONDARY_LIMIT_DELAY: Duration = Duration::from_secs(60);
 
-    // If filter requires an iteration but item has none, no match
-    if filter.starts_with('@') && iteration_title.is_none() {
+/// Give up after this many consecutive secondary rate limit responses
+const SECONDARY_LIMIT_RETRIES: u32 = 3;
+
+/// Check whether a response signals a secondary (abuse) rate limit rather than an exhausted quota.
+/// These come as a 403/429 naming the limit, or as a 200 with a `RATE_LIMITED` GraphQL error.
+fn is_secondary_rate_limit(status: reqwest::StatusCode, body: &str) -> bool {
+    if body.to_lowercase().contains("secondary rate limit") {
+        return true;
+    }
+    if !status.is_success() {
         return false;
     }
 
-    // Parse filter parts (e.g., "@current,@previous")
-    let filter_parts: Vec<&str> = filter.split(',').map(|s| s.trim()).collect();
+    #[derive(Deserialize)]
+    struct Errors {
+        errors: Option<Vec<GraphQLError>>,
+    }
+    serde_json::from_str::<Errors>(body)
+        .ok()
+        .and_then(|response| response.errors)
+        .is_some_and(|errors| {
+            errors.iter().any(|e| {
+                e.error_type.as_deref() == Some("RATE_LIMITED") && !e.message.contains("API rate limit exceeded")
+            })
+        })
+}
 
-    for part in filter_parts {
-        match part {
-            "@current" => {
-                if is_current_iteration(iteration_start) {
-                    return true;
-                }
-            }
-            "@previous" => {
-                // We need context of all iterations to determine "previous"
-                // For now, we'll use a heuristic: previous iteration ended within the last 2 weeks
-                if is_recent_past_iteration(iteration_start) {
-                    return true;
-                }
-            }
-            name => {
-                // Exact match on iteration title
-                if iteration_title == Some(name) {
-                    return true;
-                }
+/// GitHub's maximum number of items per page
+const MAX_PAGE_SIZE: u32 = 100;
+
+/// Number of project items fetched per page (overridable via DONER_PAGE_SIZE, clamped to 1-100)
+pub fn page_size() -> Result<u32> {
+    match std::env::var("DONER_PAGE_SIZE") {
+        Ok(value) => parse_page_size(&value),
+        Err(_) => Ok(MAX_PAGE_SIZE),
+    }
+}
+
+fn parse_page_size(value: &str) -> Result<u32> {
+    let size: u32 = value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("DONER_PAGE_SIZE must be a positive integer, got '{}'", value))?;
+    Ok(size.clamp(1, MAX_PAGE_SIZE))
+}
+
+/// Name of the iteration field (overridable via DONER_ITERATION_FIELD)
+pub fn iteration_field_name() -> String {
+    std::env::var("DONER_ITERATION_FIELD").unwrap_or_else(|_| "Iteration".to_string())
+}
+
+/// Date that --since/--before compare against
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum DateSource {
+    /// When the issue was closed (or created, for open issues kept with `include_open`)
+    #[default]
+    Closed,
+    /// A project date field, e.g. "Shipped Date"
+    Project(String),
+}
+
+impl DateSource {
+    /// Parse `closed` or `project:<Name>`
+    pub fn parse(value: &str) -> Result<Self> {
+        if value.eq_ignore_ascii_case("closed") {
+            return Ok(DateSource::Closed);
+        }
+        match value.split_once(':') {
+            Some((kind, name)) if kind.eq_ignore_ascii_case("project") && !name.trim().is_empty() => {
+                Ok(DateSource::Project(name.trim().to_string()))
             }
+            _ => Err(anyhow!(
+                "Invalid date field '{}'. Use 'closed' or 'project:<Name>' (e.g., project:Shipped Date)",
+                value
+            )),
+        }
+    }
+
+    /// Name of the project date field to fetch, if any
+    pub fn field_name(&self) -> Option<&str> {
+        match self {
+            DateSource::Closed => None,
+            DateSource::Project(name) => Some(name),
         }
     }
+}
 
-    false
+/// Date source for time filters: the project date field named by DONER_DATE_FIELD, else the close date
+pub fn date_source_from_env() -> DateSource {
+    match std::env::var("DONER_DATE_FIELD") {
+        Ok(name) if !name.trim().is_empty() => DateSource::Project(name.trim().to_string()),
+        _ => DateSource::Closed,
+    }
 }
 
-/// Check if the iteration start date indicates it's the current iteration.
-/// Assumes 2-week sprints by default.
-fn is_current_iteration(start_date: Option<&str>) -> bool {
-    let Some(start_str) = start_date else {
+/// Apply the issue-level filters, recording why an issue was skipped
+fn keep_issue(
+    issue: &Issue,
+    iteration_match: Option<bool>,
+    filters: &IssueFilters,
+    date_source: &DateSource,
+    seen_ids: &mut HashSet<String>,
+    stats: &mut FetchStats,
+) -> bool {
+    // Items can shift between pages while paginating; the node ID survives
+    // transfers between repositories, so key on it rather than the number
+    if !seen_ids.insert(issue.id.clone()) {
+        stats.duplicates += 1;
         return false;
+    }
+
+    // Filter by time if specified; drafts are never closed, so they are exempt unless a
+    // project date field is used. Open issues (e.g. dragged to Done by hand) only match
+    // with include_open, and then by creation date.
+    let timestamp = match date_source {
+        DateSource::Closed if filters.include_open => issue.closed_at.or(issue.created_at),
+        DateSource::Closed => issue.closed_at,
+        DateSource::Project(_) => issue.project_date.and_then(|day| crate::time_filter::local_midnight(day).ok()),
     };
+    let exempt = issue.is_draft && *date_source == DateSource::Closed;
+    let time_match = (!exempt && (filters.since.is_some() || filters.before.is_some())).then(|| {
+        timestamp.is_some_and(|timestamp| {
+            filters.since.is_none_or(|since| timestamp >= since)
+                && filters.before.is_none_or(|before| timestamp < before)
+        })
+    });
+
+    if !filters.mode.combine(iteration_match, time_match) {
+        // Attribute the skip to the iteration filter first, matching the fetch order
+        if iteration_match == Some(false) {
+            stats.filtered_by_iteration += 1;
+        } else {
+            stats.filtered_by_time += 1;
+            if timestamp.is_none() {
+                stats.missing_timestamp += 1;
+            }
+        }
+        return false;
+    }
 
-    let Ok(start) = NaiveDate::parse_from_str(start_str, "%Y-%m-%d") else {
+    // Filter by last activity, independently of the close date
+    if let Some(active_since) = filters.active_since
+        && !issue.is_draft
+        && issue.updated_at.is_none_or(|updated_at| updated_at < active_since)
+    {
+        stats.filtered_by_activity += 1;
         return false;
-    };
+    }
 
-    let today = Utc::now().date_naive();
-    let sprint_length = 14; // Default 2-week sprint
+    // Filter by the user who closed the issue
+    if let Some(login) = &filters.closed_by {
+        match &issue.closed_by {
+            Some(closer) if closer.eq_ignore_ascii_case(login) => {}
+            Some(_) => {
+                stats.filtered_by_closed_by += 1;
+                return false;
+            }
+            None => {
+                stats.filtered_by_closed_by += 1;
+                stats.closed_by_unknown += 1;
+                return false;
+            }
+        }
+    }
 
-    // Current iteration: start <= today < start + sprint_length
-    start <= today && today < start + chrono::Duration::days(sprint_length)
-}
+    // Keep only issues closed as completed, dropping not-planned and duplicate closes
+    if filters.completed_only && !issue.is_draft && issue.state_reason != Some(StateReason::Completed) {
+        stats.filtered_by_state_reason += 1;
+        return false;
+    }
 
-/// Check if iteration is from the recent past (likely previous iteration).
-/// Uses heuristic: started between 2-4 weeks ago.
-fn is_recent_past_iteration(start_date: Option<&str>) -> bool {
-    let Some(start_str) = start_date else {
+    // Drop dependency bumps and other issues opened by or assigned to bots
+    if filters.exclude_bots && issue.involves_bot() {
+        stats.filtered_by_bot += 1;
         return false;
-    };
+    }
 
-    let Ok(start) = NaiveDate::parse_from_str(start_str, "%Y-%m-%d") else {
+    // Filter by milestone deadline; issues without a due milestone can't be shown to meet it
+    if let Some(due_before) = filters.milestone_due_before
+        && issue.milestone_due_on.is_none_or(|due_on| due_on >= due_before)
+    {
+        stats.filtered_by_milestone_due += 1;
         return false;
-    };
+    }
 
-    let today = Utc::now().date_naive();
-    let sprint_length = 14;
+    // Filter by title patterns
+    let title_included = filters.title_match.as_ref().is_none_or(|re| re.is_match(&issue.title));
+    let title_excluded = filters.title_exclude.as_ref().is_some_and(|re| re.is_match(&issue.title));
+    if !title_included || title_excluded {
+        stats.filtered_by_title += 1;
+        return false;
+    }
+
+    true
+}
+
+/// How the iteration and time filters combine when both are given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum FilterMode {
+    /// Issues must match both filters
+    #[default]
+    And,
+    /// Issues must match at least one filter
+    Or,
+}
+
+impl FilterMode {
+    /// Combine filter results; `None` means the filter was not given
+    pub fn combine(self, iteration: Option<bool>, time: Option<bool>) -> bool {
+        match (self, iteration, time) {
+            (_, None, None) => true,
+            (_, Some(matched), None) | (_, None, Some(matched)) => matched,
+            (FilterMode::And, Some(a), Some(b)) => a && b,
+            (FilterMode::Or, Some(a), Some(b)) => a || b,
+        }
+    }
+}
+
+/// Which items to keep with respect to the project's archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArchivedItems {
+    /// Skip archived items, as the GitHub UI hides them
+    #[default]
+    Exclude,
+    /// Keep archived and active items
+    Include,
+    /// Keep only archived items
+    Only,
+}
+
+impl ArchivedItems {
+    /// Whether an item with the given archive state is kept
+    pub fn keeps(self, is_archived: bool) -> bool {
+        match self {
+            ArchivedItems::Exclude => !is_archived,
+            ArchivedItems::Include => true,
+            ArchivedItems::Only => is_archived,
+        }
+    }
+}
 
-    // Previous iteration: started 2-4 weeks ago
-    let prev_start = today - chrono::Duration::days(sprint_length * 2);
-    let prev_end = today - chrono::Duration::days(sprint_length);
+/// Filters applied while fetching project issues
+#[derive(Debug, Clone, Default)]
+pub struct IssueFilters {
+    /// Status column the issues must be in
+    pub column: String,
+    /// Only keep issues closed at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only keep issues closed before this time (combined with `since` to bound both ends)
+    pub before: Option<DateTime<Utc>>,
+    /// Only keep issues updated at or after this time (combined with `since` as AND)
+    pub active_since: Option<DateTime<Utc>>,
+    /// Iteration filter expression (see `matches_iteration_filter`)
+    pub iteration: Option<String>,
+    /// Only keep issues closed by this user
+    pub closed_by: Option<String>,
+    /// Only keep issues whose milestone is due before this time
+    pub milestone_due_before: Option<DateTime<Utc>>,
+    /// Only keep issues closed as completed (not as not-planned or duplicate)
+    pub completed_only: bool,
+    /// Stop paginating once this many issues have matched
+    pub sample: Option<usize>,
+    /// Record the archived items that are skipped in `FetchStats::archived_items`
+    pub list_archived: bool,
+    /// Drop issues opened by or assigned to bot accounts
+    pub exclude_bots: bool,
+    /// Keep draft items instead of counting them as non-issues
+    pub include_drafts: bool,
+    /// Whether archived items are skipped, kept, or the only ones kept
+    pub archived: ArchivedItems,
+    /// Match open issues against `since` by creation date instead of dropping them
+    pub include_open: bool,
+    /// Fetch tracked-by and duplicate relationships so linked issues can be merged
+    pub include_links: bool,
+    /// Custom single-select fields to fetch into `Issue::fields` (e.g. for --group-by field:<Name>)
+    pub fields: Vec<String>,
+    /// How `iteration` and `since` combine
+    pub mode: FilterMode,
+    /// Status field name for this run, overriding DONER_STATUS_FIELD
+    pub status_field: Option<String>,
+    /// Iteration field name for this run, overriding DONER_ITERATION_FIELD
+    pub iteration_field: Option<String>,
+    /// Date the time filters compare against for this run, overriding DONER_DATE_FIELD
+    pub date_field: Option<DateSource>,
+    /// Only keep issues whose title matches
+    pub title_match: Option<Regex>,
+    /// Drop issues whose title matches
+    pub title_exclude: Option<Regex>,
+}
+
+impl IssueFilters {
+    /// Name of the status field: the per-run override, else `status_field_name()`
+    pub fn status_field(&self) -> String {
+        self.status_field.clone().unwrap_or_else(status_field_name)
+    }
+
+    /// Name of the iteration field: the per-run override, else `iteration_field_name()`
+    pub fn iteration_field(&self) -> String {
+        self.iteration_field.clone().unwrap_or_else(iteration_field_name)
+    }
+
+    /// Date the time filters compare against: the per-run override, else `date_source_from_env()`
+    pub fn date_source(&self) -> DateSource {
+        self.date_field.clone().unwrap_or_else(date_source_from_env)
+    }
+
+    /// Widen the since/before window to also cover `other`'s, e.g. when `@current` resolved
+    /// differently on each board. An open end on either side stays open
+    pub fn widen_window(&mut self, other: &IssueFilters) {
+        self.since = self.since.zip(other.since).map(|(a, b)| a.min(b));
+        self.before = self.before.zip(other.before).map(|(a, b)| a.max(b));
+    }
+}
+
+/// Compile a title filter pattern, naming the flag it came from on error
+pub fn parse_title_regex(pattern: &str, flag: &str) -> Result<Regex> {
+    Regex::new(pattern).map_err(|e| anyhow!("Invalid {} pattern '{}': {}", flag, pattern, e))
+}
 
-    start >= prev_start && start < prev_end
+/// An archived item skipped by a fetch, recorded when `IssueFilters::list_archived` is set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedItem {
+    pub title: String,
+    /// Drafts have no URL
+    pub url: Option<String>,
+    /// Status column the item was archived in
+    pub column: Option<String>,
 }
 
 #[derive(Debug, Default)]
 pub struct FetchStats {
     pub total_items: usize,
+    /// Item count reported by GitHub (`items.totalCount`)
+    pub reported_total: usize,
+    /// Archived items seen (skipped unless archived items were requested)
     pub archived: usize,
+    /// The skipped archived items, when listing them was requested
+    pub archived_items: Vec<ArchivedItem>,
+    /// Active items skipped because only archived items were requested
+    pub skipped_active: usize,
     pub wrong_column: usize,
     pub not_issue: usize,
     pub filtered_by_time: usize,
+    /// Issues excluded by the time filter because they have no usable timestamp
+    pub missing_timestamp: usize,
+    pub filtered_by_activity: usize,
     pub filtered_by_iteration: usize,
+    pub filtered_by_closed_by: usize,
+    /// Issues excluded by --closed-by because the closing actor is unknown
+    pub closed_by_unknown: usize,
+    pub filtered_by_title: usize,
+    /// Issues excluded by --milestone-due-before, including those without a due milestone
+    pub filtered_by_milestone_due: usize,
+    /// Issues excluded by --completed-only, including open ones
+    pub filtered_by_state_reason: usize,
+    /// Issues excluded by --no-bots
+    pub filtered_by_bot: usize,
+    /// Issues seen more than once (skipped)
+    pub duplicates: usize,
+    /// Unarchived items with any value in the status field
+    pub with_status: usize,
+    /// Pagination stopped at `IssueFilters::sample` with items left unread
+    pub sampled: bool,
+    /// Rate-limit points spent fetching items (only tracked in verbose mode)
+    pub query_cost: u32,
     pub columns_seen: HashSet<String>,
     pub iterations_seen: HashSet<String>,
 }
 
+impl FetchStats {
+    /// One-line account of a fetch, e.g. `12 issues from 'Done' (filtered 40 by column, 3 by time)`
+    pub fn summary(&self, kept: usize, column: &str) -> String {
+        let filtered: Vec<String> = [
+            (self.wrong_column, "by column"),
+            (self.filtered_by_iteration, "by iteration"),
+            (self.filtered_by_time, "by time"),
+            (self.filtered_by_activity, "by activity"),
+            (self.filtered_by_closed_by, "by closer"),
+            (self.filtered_by_title, "by title"),
+            (self.filtered_by_milestone_due, "by milestone due date"),
+            (self.filtered_by_state_reason, "by close reason"),
+            (self.filtered_by_bot, "as bot issues"),
+            (self.duplicates, "as duplicates"),
+        ]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, reason)| format!("{} {}", count, reason))
+        .collect();
+
+        let noun = if kept == 1 { "issue" } else { "issues" };
+        if filtered.is_empty() {
+            format!("{} {} from '{}'", kept, noun, column)
+        } else {
+            format!("{} {} from '{}' (filtered {})", kept, noun, column, filtered.join(", "))
+        }
+    }
+
+    /// Add the counts of another fetch, e.g. from a second project
+    pub fn absorb(&mut self, other: FetchStats) {
+        self.total_items += other.total_items;
+        self.reported_total += other.reported_total;
+        self.archived += other.archived;
+        self.archived_items.extend(other.archived_items);
+        self.skipped_active += other.skipped_active;
+        self.wrong_column += other.wrong_column;
+        self.not_issue += other.not_issue;
+        self.filtered_by_time += other.filtered_by_time;
+        self.missing_timestamp += other.missing_timestamp;
+        self.filtered_by_activity += other.filtered_by_activity;
+        self.filtered_by_iteration += other.filtered_by_iteration;
+        self.filtered_by_closed_by += other.filtered_by_closed_by;
+        self.closed_by_unknown += other.closed_by_unknown;
+        self.filtered_by_title += other.filtered_by_title;
+        self.filtered_by_milestone_due += other.filtered_by_milestone_due;
+        self.filtered_by_state_reason += other.filtered_by_state_reason;
+        self.filtered_by_bot += other.filtered_by_bot;
+        self.duplicates += other.duplicates;
+        self.with_status += other.with_status;
+        self.sampled |= other.sampled;
+        self.query_cost += other.query_cost;
+        self.columns_seen.extend(other.columns_seen);
+        self.iterations_seen.extend(other.iterations_seen);
+    }
+}
+
 pub struct GitHubClient {
     client: Client,
-    token: String,
+    /// Replaced when the user re-authenticates mid-run
+    token: RwLock<String>,
+    graphql_url: String,
+    verbose: bool,
+    /// Wait before retrying after a secondary rate limit (shortened in tests)
+    secondary_limit_delay: Duration,
+    reauth: Option<ReauthHook>,
+    fixtures: Option<Fixtures>,
+}
+
+/// Whether GraphQL responses are written to or served from a fixture directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FixtureMode {
+    Record,
+    Replay,
+}
+
+/// Numbered response files (`0001.json`, `0002.json`, ...) in the order the queries ran
+struct Fixtures {
+    mode: FixtureMode,
+    dir: PathBuf,
+    next: AtomicUsize,
+}
+
+impl Fixtures {
+    fn new(mode: FixtureMode, dir: PathBuf) -> Self {
+        Self { mode, dir, next: AtomicUsize::new(1) }
+    }
+
+    fn next_path(&self) -> PathBuf {
+        self.dir.join(format!("{:04}.json", self.next.fetch_add(1, Ordering::SeqCst)))
+    }
 }
 
 impl GitHubClient {
-    pub fn new(token: &str) -> Self {
-        Self {
-            client: Client::new(),
-            token: token.to_string(),
+    pub fn new(token: &str) -> Result<Self> {
+        Self::with_graphql_url(token, &crate::http::graphql_url(&crate::http::api_url()?))
+    }
+
+    /// Create a client for the configured API host. DONER_RECORD_DIR writes every response to a
+    /// fixture directory and DONER_REPLAY_DIR serves them back without touching the network, so
+    /// `token` is only called when the client will actually talk to GitHub
+    pub async fn from_env<F, Fut>(token: F) -> Result<Self>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<String>>,
+    {
+        let var = |name| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+        match (var("DONER_RECORD_DIR"), var("DONER_REPLAY_DIR")) {
+            (Some(_), Some(_)) => Err(anyhow!("Set only one of DONER_RECORD_DIR and DONER_REPLAY_DIR")),
+            (None, Some(dir)) => Ok(Self::new("")?.replay_from(dir)),
+            (Some(dir), None) => Self::new(&token().await?)?.record_to(dir),
+            (None, None) => Self::new(&token().await?),
         }
     }
 
-    /// Resolve a project identifier to a GraphQL node ID
+    /// Create a client that sends GraphQL queries to a custom endpoint
+    pub fn with_graphql_url(token: &str, graphql_url: &str) -> Result<Self> {
+        Ok(Self {
+            client: crate::http::client()?,
+            token: RwLock::new(token.to_string()),
+            graphql_url: graphql_url.to_string(),
+            verbose: false,
+            secondary_limit_delay: SECONDARY_LIMIT_DELAY,
+            reauth: None,
+            fixtures: None,
+        })
+    }
+
+    /// Write each successful GraphQL response, token redacted, to a numbered file in `dir`
+    pub fn record_to(mut self, dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create record directory {}", dir.display()))?;
+        self.fixtures = Some(Fixtures::new(FixtureMode::Record, dir));
+        Ok(self)
+    }
+
+    /// Serve GraphQL responses from the numbered files in `dir` instead of sending requests
+    pub fn replay_from(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.fixtures = Some(Fixtures::new(FixtureMode::Replay, dir.into()));
+        self
+    }
+
+    /// Ask `hook` for a new token when GitHub rejects the current one, then retry the request
+    /// so a long fetch resumes instead of starting over
+    pub fn on_auth_expired(mut self, hook: ReauthHook) -> Self {
+        self.reauth = Some(hook);
+        self
+    }
+
+    fn token(&self) -> String {
+        self.token.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Log the rate-limit cost of each page fetched
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Resolve a project identifier to a GraphQL node ID and board URL
     /// Supports:
-    /// - Direct node ID (starts with "PVT_")
+    /// - Direct node ID (starts with "PVT_"); the URL is not looked up
     /// - Owner/number format (e.g., "myorg/5" or "myuser/3")
-    pub async fn resolve_project_id(&self, project_id: &str) -> Result<String> {
-        // If it looks like a node ID, return as-is
+    pub async fn resolve_project_id(&self, project_id: &str) -> Result<ResolvedProject> {
+        // If it looks like a node ID, return it once it passes a format check
         if project_id.starts_with("PVT_") {
-            return Ok(project_id.to_string());
+            validate_node_id(project_id)?;
+            return Ok(ResolvedProject {
+                node_id: project_id.to_string(),
+                url: None,
+            });
         }
 
         // Parse owner/number format
@@ -151,21 +745,24 @@ impl GitHubClient {
     }
 
     /// Look up a project's node ID by owner and project number
-    async fn lookup_project_id(&self, owner: &str, number: u32) -> Result<String> {
-        // Try organization first, then user
-        if let Ok(id) = self.lookup_org_project(owner, number).await {
-            return Ok(id);
+    async fn lookup_project_id(&self, owner: &str, number: u32) -> Result<ResolvedProject> {
+        // Try organization first, then user; a permission problem is not worth retrying
+        match self.lookup_org_project(owner, number).await {
+            Ok(id) => return Ok(id),
+            Err(e) if e.is::<ProjectAccessError>() => return Err(e),
+            Err(_) => {}
         }
 
         self.lookup_user_project(owner, number).await
     }
 
-    async fn lookup_org_project(&self, org: &str, number: u32) -> Result<String> {
+    async fn lookup_org_project(&self, org: &str, number: u32) -> Result<ResolvedProject> {
         let query = r#"
             query($org: String!, $number: Int!) {
                 organization(login: $org) {
                     projectV2(number: $number) {
                         id
+                        url
                     }
                 }
             }
@@ -192,6 +789,7 @@ impl GitHubClient {
         #[derive(Deserialize)]
         struct ProjectId {
             id: String,
+            url: Option<String>,
         }
 
         let parsed: GraphQLResponse<OrgData> =
@@ -199,6 +797,9 @@ impl GitHubClient {
 
         // Check for GraphQL errors
         if let Some(errors) = &parsed.errors {
+            if is_access_error(errors) {
+                return Err(graphql_error(errors, Some(org)));
+            }
             let messages: Vec<_> = errors.iter().map(|e| e.message.as_str()).collect();
             return Err(anyhow!("GitHub API error: {}", messages.join(", ")));
         }
@@ -206,7 +807,10 @@ impl GitHubClient {
         if let Some(data) = parsed.data {
             if let Some(org_data) = data.organization {
                 if let Some(project) = org_data.project_v2 {
-                    return Ok(project.id);
+                    return Ok(ResolvedProject {
+                        node_id: project.id,
+                        url: project.url,
+                    });
                 }
                 return Err(anyhow!(
                     "Project #{} not found in organization '{}'. Check the project number and your token permissions (needs 'read:project' scope).",
@@ -222,12 +826,13 @@ impl GitHubClient {
         Err(anyhow!("Organization project not found"))
     }
 
-    async fn lookup_user_project(&self, user: &str, number: u32) -> Result<String> {
+    async fn lookup_user_project(&self, user: &str, number: u32) -> Result<ResolvedProject> {
         let query = r#"
             query($user: String!, $number: Int!) {
                 user(login: $user) {
                     projectV2(number: $number) {
                         id
+                        url
                     }
                 }
             }
@@ -254,17 +859,26 @@ impl GitHubClient {
         #[derive(Deserialize)]
         struct ProjectId {
             id: String,
+            url: Option<String>,
         }
 
         let parsed: GraphQLResponse<UserData> =
             serde_json::from_str(&response).context("Failed to parse GitHub response")?;
 
-        if let Some(data) = parsed.data {
-            if let Some(user) = data.user {
-                if let Some(project) = user.project_v2 {
-                    return Ok(project.id);
-                }
-            }
+        if let Some(errors) = &parsed.errors
+            && is_access_error(errors)
+        {
+            return Err(graphql_error(errors, Some(user)));
+        }
+
+        if let Some(data) = parsed.data
+            && let Some(user) = data.user
+            && let Some(project) = user.project_v2
+        {
+            return Ok(ResolvedProject {
+                node_id: project.id,
+                url: project.url,
+            });
         }
 
         Err(anyhow!(
@@ -272,134 +886,86 @@ impl GitHubClient {
         ))
     }
 
-    async fn execute_query(
-        &self,
-        query: &str,
-        variables: &serde_json::Value,
-    ) -> Result<String> {
-        let response = self
-            .client
-            .post(GITHUB_GRAPHQL_URL)
-            .header("Authorization", format!("Bearer {}", self.token))
-            .header("User-Agent", "doner-cli")
-            .json(&json!({
-                "query": query,
-                "variables": variables
-            }))
-            .send()
-            .await
-            .context("Failed to send request to GitHub API")?;
+    /// List the open projects of a user or organization, sorted by number
+    pub async fn fetch_owner_projects(&self, owner: &str) -> Result<Vec<OwnerProject>> {
+        let query = r#"
+            query($owner: String!) {
+                repositoryOwner(login: $owner) {
+                    ... on ProjectV2Owner {
+                        projectsV2(first: 100) {
+                            nodes {
+                                number
+                                title
+                                closed
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
 
-        let status = response.status();
-        let body = response.text().await?;
+        let response = self.execute_query(query, &json!({ "owner": owner })).await?;
 
-        if !status.is_success() {
-            return Err(anyhow!("GitHub API error ({}): {}", status, body));
+        #[derive(Deserialize)]
+        struct OwnerData {
+            #[serde(rename = "repositoryOwner")]
+            repository_owner: Option<OwnerNode>,
         }
 
-        Ok(body)
-    }
+        #[derive(Deserialize)]
+        struct OwnerNode {
+            #[serde(rename = "projectsV2")]
+            projects_v2: Option<ProjectConnection>,
+        }
 
-    pub async fn fetch_project_issues(
-        &self,
-        project_node_id: &str,
-        column_name: &str,
-        since: Option<DateTime<Utc>>,
-        iteration_filter: Option<&str>,
-        collect_stats: bool,
-    ) -> Result<(Vec<Issue>, FetchStats)> {
-        let mut all_issues = Vec::new();
-        let mut cursor: Option<String> = None;
-        let mut stats = FetchStats::default();
+        #[derive(Deserialize)]
+        struct ProjectConnection {
+            nodes: Vec<ProjectNode>,
+        }
 
-        loop {
-            let (issues, page_info, page_stats) = self
-                .fetch_project_items_page(project_node_id, column_name, iteration_filter, cursor.as_deref(), collect_stats)
-                .await?;
-
-            stats.total_items += page_stats.total_items;
-            stats.archived += page_stats.archived;
-            stats.wrong_column += page_stats.wrong_column;
-            stats.not_issue += page_stats.not_issue;
-            stats.filtered_by_iteration += page_stats.filtered_by_iteration;
-            stats.columns_seen.extend(page_stats.columns_seen);
-            stats.iterations_seen.extend(page_stats.iterations_seen);
-
-            for issue in issues {
-                // Filter by time if specified
-                if let Some(since_time) = since {
-                    if let Some(closed_at) = issue.closed_at {
-                        if closed_at < since_time {
-                            stats.filtered_by_time += 1;
-                            continue;
-                        }
-                    } else {
-                        // If no closed_at and we have a time filter, skip
-                        stats.filtered_by_time += 1;
-                        continue;
-                    }
-                }
-                all_issues.push(issue);
-            }
+        #[derive(Deserialize)]
+        struct ProjectNode {
+            number: u32,
+            title: String,
+            closed: bool,
+        }
 
-            if !page_info.has_next_page {
-                break;
-            }
-            cursor = page_info.end_cursor;
+        let parsed: GraphQLResponse<OwnerData> =
+            serde_json::from_str(&response).context("Failed to parse GitHub response")?;
+
+        if let Some(errors) = parsed.errors {
+            return Err(graphql_error(&errors, Some(owner)));
         }
 
-        Ok((all_issues, stats))
+        let connection = parsed
+            .data
+            .and_then(|d| d.repository_owner)
+            .and_then(|o| o.projects_v2)
+            .ok_or_else(|| anyhow!("Owner '{}' not found or not accessible.", owner))?;
+
+        let mut projects: Vec<_> = connection
+            .nodes
+            .into_iter()
+            .filter(|p| !p.closed)
+            .map(|p| OwnerProject {
+                number: p.number,
+                title: p.title,
+            })
+            .collect();
+        projects.sort_by_key(|p| p.number);
+        Ok(projects)
     }
 
-    async fn fetch_project_items_page(
-        &self,
-        project_node_id: &str,
-        column_name: &str,
-        iteration_filter: Option<&str>,
-        cursor: Option<&str>,
-        collect_stats: bool,
-    ) -> Result<(Vec<Issue>, PageInfo, FetchStats)> {
+    /// Fetch the names of all fields configured on the project
+    pub async fn fetch_field_names(&self, project_node_id: &str) -> Result<Vec<String>> {
         let query = r#"
-            query($projectId: ID!, $cursor: String, $statusField: String!, $iterationField: String!) {
+            query($projectId: ID!) {
                 node(id: $projectId) {
                     ... on ProjectV2 {
-                        items(first: 100, after: $cursor) {
-                            pageInfo {
-                                hasNextPage
-                                endCursor
-                            }
+                        fields(first: 100) {
                             nodes {
-                                id
-                                isArchived
-                                fieldValueByName(name: $statusField) {
-                                    ... on ProjectV2ItemFieldSingleSelectValue {
-                                        __typename
-                                        name
-                                    }
-                                }
-                                iteration: fieldValueByName(name: $iterationField) {
-                                    ... on ProjectV2ItemFieldIterationValue {
-                                        __typename
-                                        title
-                                        startDate
-                                    }
-                                }
-                                content {
-                                    __typename
-                                    ... on Issue {
-                                        number
-                                        title
-                                        url
-                                        closedAt
-                                        repository {
-                                            nameWithOwner
-                                        }
-                                        parent {
-                                            number
-                                            title
-                                            url
-                                        }
-                                    }
+                                ... on ProjectV2FieldCommon {
+                                    name
                                 }
                             }
                         }
@@ -408,48 +974,848 @@ impl GitHubClient {
             }
         "#;
 
-        // Allow overriding field names via environment variables
-        let status_field = std::env::var("DONER_STATUS_FIELD").unwrap_or_else(|_| "Status".to_string());
-        let iteration_field = std::env::var("DONER_ITERATION_FIELD").unwrap_or_else(|_| "Iteration".to_string());
+        let response = self.execute_query(query, &json!({ "projectId": project_node_id })).await?;
 
-        let variables = json!({
-            "projectId": project_node_id,
-            "cursor": cursor,
-            "statusField": status_field,
-            "iterationField": iteration_field
+        #[derive(Deserialize)]
+        struct FieldsData {
+            node: Option<FieldsNode>,
+        }
+
+        #[derive(Deserialize)]
+        struct FieldsNode {
+            fields: FieldConnection,
+        }
+
+        #[derive(Deserialize)]
+        struct FieldConnection {
+            nodes: Vec<FieldName>,
+        }
+
+        #[derive(Deserialize)]
+        struct FieldName {
+            name: Option<String>,
+        }
+
+        let parsed: GraphQLResponse<FieldsData> =
+            serde_json::from_str(&response).context("Failed to parse GitHub response")?;
+
+        if let Some(errors) = parsed.errors {
+            return Err(self.node_error(&errors, project_node_id));
+        }
+
+        let node = parsed
+            .data
+            .and_then(|d| d.node)
+            .ok_or_else(|| self.project_not_found(project_node_id))?;
+
+        Ok(node.fields.nodes.into_iter().filter_map(|f| f.name).collect())
+    }
+
+    /// Explain that no item has a status value, listing the project's fields when they can be fetched
+    async fn missing_status_field_error(&self, project_node_id: &str, status_field: &str) -> anyhow::Error {
+        let hint = match self.fetch_field_names(project_node_id).await {
+            Ok(names) if !names.is_empty() => format!("Available fields: {}", names.join(", ")),
+            _ => "Run 'doner columns' to check the field configuration".to_string(),
+        };
+        anyhow!(
+            "No item in the project has a value for the \"{}\" field, so none can match a column. \
+             The field may be missing or misnamed; pass --status-field or set DONER_STATUS_FIELD \
+             to the name of your status field.\n  {}",
+            status_field,
+            hint
+        )
+    }
+
+    /// Fetch the options of the project's status field, in board order
+    pub async fn fetch_status_options(&self, project_node_id: &str) -> Result<Vec<String>> {
+        let query = r#"
+            query($projectId: ID!, $statusField: String!) {
+                node(id: $projectId) {
+                    ... on ProjectV2 {
+                        field(name: $statusField) {
+                            __typename
+                            ... on ProjectV2SingleSelectField {
+                                options {
+                                    name
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let status_field = status_field_name();
+        let variables = json!({
+            "projectId": project_node_id,
+            "statusField": status_field
         });
 
         let response = self.execute_query(query, &variables).await?;
 
-        let parsed: GraphQLResponse<ProjectData> =
+        #[derive(Deserialize)]
+        struct FieldData {
+            node: Option<FieldNode>,
+        }
+
+        #[derive(Deserialize)]
+        struct FieldNode {
+            field: Option<StatusField>,
+        }
+
+        #[derive(Deserialize)]
+        struct StatusField {
+            options: Option<Vec<StatusOption>>,
+        }
+
+        #[derive(Deserialize)]
+        struct StatusOption {
+            name: String,
+        }
+
+        let parsed: GraphQLResponse<FieldData> =
             serde_json::from_str(&response).context("Failed to parse GitHub response")?;
 
         if let Some(errors) = parsed.errors {
-            let messages: Vec<_> = errors.iter().map(|e| e.message.as_str()).collect();
-            return Err(anyhow!("GraphQL errors: {}", messages.join(", ")));
+            return Err(self.node_error(&errors, project_node_id));
         }
 
-        let project = parsed
+        let node = parsed
             .data
             .and_then(|d| d.node)
-            .ok_or_else(|| anyhow!("Project not found. Make sure the project ID is correct."))?;
+            .ok_or_else(|| self.project_not_found(project_node_id))?;
 
-        let mut issues = Vec::new();
-        let mut stats = FetchStats::default();
-        stats.total_items = project.items.nodes.len();
+        let options = node.field.and_then(|f| f.options).ok_or_else(|| {
+            anyhow!(
+                "Field \"{}\" not found or is not a single-select field. Set DONER_STATUS_FIELD to the name of your status field.",
+                status_field
+            )
+        })?;
 
-        for item in project.items.nodes {
-            // Skip archived items (hidden in GitHub UI)
-            if item.is_archived {
-                stats.archived += 1;
+        Ok(options.into_iter().map(|o| o.name).collect())
+    }
+
+    /// Fetch all iterations (completed and upcoming) of the project's iteration field,
+    /// sorted by start date
+    pub async fn fetch_iterations(&self, project_node_id: &str, iteration_field: &str) -> Result<Vec<Iteration>> {
+        let query = r#"
+            query($projectId: ID!, $iterationField: String!) {
+                node(id: $projectId) {
+                    ... on ProjectV2 {
+                        field(name: $iterationField) {
+                            __typename
+                            ... on ProjectV2IterationField {
+                                configuration {
+                                    iterations {
+                                        title
+                                        startDate
+                                        duration
+                                    }
+                                    completedIterations {
+                                        title
+                                        startDate
+                                        duration
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "projectId": project_node_id,
+            "iterationField": iteration_field
+        });
+
+        let response = self.execute_query(query, &variables).await?;
+
+        #[derive(Deserialize)]
+        struct FieldData {
+            node: Option<FieldNode>,
+        }
+
+        #[derive(Deserialize)]
+        struct FieldNode {
+            field: Option<IterationField>,
+        }
+
+        #[derive(Deserialize)]
+        struct IterationField {
+            configuration: Option<IterationConfiguration>,
+        }
+
+        #[derive(Deserialize)]
+        struct IterationConfiguration {
+            iterations: Vec<Iteration>,
+            #[serde(rename = "completedIterations")]
+            completed_iterations: Vec<Iteration>,
+        }
+
+        let parsed: GraphQLResponse<FieldData> =
+            serde_json::from_str(&response).context("Failed to parse GitHub response")?;
+
+        if let Some(errors) = parsed.errors {
+            return Err(self.node_error(&errors, project_node_id));
+        }
+
+        let node = parsed
+            .data
+            .and_then(|d| d.node)
+            .ok_or_else(|| self.project_not_found(project_node_id))?;
+
+        let config = node.field.and_then(|f| f.configuration).ok_or_else(|| {
+            anyhow!(
+                "Field \"{}\" not found or is not an iteration field. Set DONER_ITERATION_FIELD to the name of your iteration field.",
+                iteration_field
+            )
+        })?;
+
+        let mut iterations = config.completed_iterations;
+        iterations.extend(config.iterations);
+        iterations.sort_by_key(|it| it.start_date);
+
+        Ok(iterations)
+    }
+
+    /// Create a draft release for `tag` in `repo` (owner/name) with `body` as its notes,
+    /// returning the release's URL
+    pub async fn create_draft_release(&self, repo: &str, tag: &str, body: &str) -> Result<String> {
+        let api_url = crate::http::rest_url(&self.graphql_url);
+        let response = self
+            .client
+            .post(format!("{}/repos/{}/releases", api_url, repo))
+            .header("Authorization", format!("Bearer {}", self.token()))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "doner-cli")
+            .json(&json!({
+                "tag_name": tag,
+                "name": tag,
+                "body": body,
+                "draft": true
+            }))
+            .send()
+            .await
+            .map_err(|e| self.redacted(crate::http::request_error(e, "Failed to send request to GitHub API")))?;
+
+        let status = response.status();
+        let text = crate::http::redact(&response.text().await.unwrap_or_default(), &self.token());
+        if !status.is_success() {
+            return Err(anyhow!("Failed to create release {} in {} (HTTP {}): {}", tag, repo, status, text));
+        }
+
+        #[derive(Deserialize)]
+        struct Release {
+            html_url: String,
+        }
+        let release: Release = serde_json::from_str(&text).context("Failed to parse GitHub release response")?;
+        Ok(release.html_url)
+    }
+
+    /// When `tag` was released in `repo` (owner/name): the GitHub Release's publish time, or the
+    /// date of the tagged commit when the tag has no published release
+    pub async fn tag_date(&self, repo: &str, tag: &str) -> Result<DateTime<Utc>> {
+        let (owner, name) = repo
+            .split_once('/')
+            .filter(|(owner, name)| !owner.is_empty() && !name.is_empty() && !name.contains('/'))
+            .ok_or_else(|| anyhow!("Invalid repository '{}'. Use owner/name", repo))?;
+
+        let query = r#"
+            query($owner: String!, $name: String!, $tag: String!, $ref: String!) {
+                repository(owner: $owner, name: $name) {
+                    release(tagName: $tag) {
+                        publishedAt
+                    }
+                    ref(qualifiedName: $ref) {
+                        target {
+                            ... on Commit { committedDate }
+                            ... on Tag { target { ... on Commit { committedDate } } }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "owner": owner,
+            "name": name,
+            "tag": tag,
+            "ref": format!("refs/tags/{}", tag)
+        });
+
+        let response = self.execute_query(query, &variables).await?;
+
+        #[derive(Deserialize)]
+        struct Data {
+            repository: Option<Repository>,
+        }
+
+        #[derive(Deserialize)]
+        struct Repository {
+            release: Option<Release>,
+            #[serde(rename = "ref")]
+            tag_ref: Option<TagRef>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Release {
+            published_at: Option<DateTime<Utc>>,
+        }
+
+        #[derive(Deserialize)]
+        struct TagRef {
+            target: Option<Target>,
+        }
+
+        /// A lightweight tag points at the commit, an annotated one at a Tag object wrapping it
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Target {
+            committed_date: Option<DateTime<Utc>>,
+            target: Option<Box<Target>>,
+        }
+
+        let parsed: GraphQLResponse<Data> =
+            serde_json::from_str(&response).context("Failed to parse GitHub response")?;
+        if let Some(errors) = &parsed.errors {
+            if is_access_error(errors) {
+                return Err(graphql_error(errors, Some(owner)));
+            }
+            let messages: Vec<_> = errors.iter().map(|e| e.message.as_str()).collect();
+            return Err(anyhow!("GitHub API error: {}", messages.join(", ")));
+        }
+
+        let repository = parsed
+            .data
+            .and_then(|data| data.repository)
+            .ok_or_else(|| anyhow!("Repository '{}' not found or not accessible", repo))?;
+        let released = repository.release.and_then(|release| release.published_at);
+        let committed = repository.tag_ref.and_then(|tag_ref| tag_ref.target).and_then(|target| {
+            target
+                .committed_date
+                .or_else(|| target.target.and_then(|inner| inner.committed_date))
+        });
+
+        released
+            .or(committed)
+            .ok_or_else(|| anyhow!("Tag '{}' not found in {}: no published release or git tag has that name", tag, repo))
+    }
+
+    /// Error for a node lookup that came back empty
+    fn project_not_found(&self, project_node_id: &str) -> anyhow::Error {
+        ProjectNotFound {
+            node_id: project_node_id.to_string(),
+            host: crate::http::api_host(&self.graphql_url),
+        }
+        .into()
+    }
+
+    /// Turn GraphQL errors from a project lookup into an error, reporting an unresolvable node ID
+    /// as `ProjectNotFound`
+    fn node_error(&self, errors: &[GraphQLError], project_node_id: &str) -> anyhow::Error {
+        if errors.iter().any(|e| e.error_type.as_deref() == Some("NOT_FOUND")) {
+            return self.project_not_found(project_node_id);
+        }
+        graphql_error(errors, None)
+    }
+
+    /// Mask the token in an error message
+    fn redacted(&self, error: anyhow::Error) -> anyhow::Error {
+        anyhow!(crate::http::redact(&format!("{:#}", error), &self.token()))
+    }
+
+    /// Run a GraphQL query, waiting out secondary rate limits before retrying and
+    /// re-authenticating once if the token is rejected
+    async fn execute_query(
+        &self,
+        query: &str,
+        variables: &serde_json::Value,
+    ) -> Result<String> {
+        let mut attempt = 0;
+        let mut reauthenticated = false;
+        loop {
+            let (status, retry_after, body) = self.send_query(query, variables).await?;
+            if attempt < SECONDARY_LIMIT_RETRIES && is_secondary_rate_limit(status, &body) {
+                attempt += 1;
+                let delay = retry_after.map_or(self.secondary_limit_delay, |d| d.max(self.secondary_limit_delay));
+                if self.verbose {
+                    eprintln!(
+                        "Verbose: hit GitHub's secondary rate limit, retrying in {}s (attempt {} of {})",
+                        delay.as_secs(),
+                        attempt,
+                        SECONDARY_LIMIT_RETRIES
+                    );
+                }
+                tokio::time::sleep(delay).await;
                 continue;
             }
+            if status == reqwest::StatusCode::UNAUTHORIZED
+                && !reauthenticated
+                && let Some(reauth) = &self.reauth
+                && let Some(token) = reauth()?
+            {
+                reauthenticated = true;
+                *self.token.write().unwrap_or_else(|e| e.into_inner()) = token;
+                continue;
+            }
+            return Self::check_response(status, body);
+        }
+    }
+
+    /// Send a GraphQL request, returning the status, any Retry-After delay and the redacted body
+    async fn send_query(
+        &self,
+        query: &str,
+        variables: &serde_json::Value,
+    ) -> Result<(reqwest::StatusCode, Option<Duration>, String)> {
+        if let Some(fixtures) = &self.fixtures
+            && fixtures.mode == FixtureMode::Replay
+        {
+            let path = fixtures.next_path();
+            let body = std::fs::read_to_string(&path).with_context(|| {
+                format!("No recorded response {} to replay (the run sent more queries than were recorded)", path.display())
+            })?;
+            return Ok((reqwest::StatusCode::OK, None, body));
+        }
+
+        let response = self
+            .client
+            .post(&self.graphql_url)
+            .header("Authorization", format!("Bearer {}", self.token()))
+            .header("User-Agent", "doner-cli")
+            .json(&json!({
+                "query": query,
+                "variables": variables
+            }))
+            .send()
+            .await
+            .map_err(|e| self.redacted(crate::http::request_error(e, "Failed to send request to GitHub API")))?;
+
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs);
+        let body = response
+            .text()
+            .await
+            .map_err(|e| self.redacted(crate::http::request_error(e, "Failed to read GitHub API response")))?;
+
+        // Everything derived from the body (errors included) is token-free from here on
+        let body = crate::http::redact(&body, &self.token());
+        if let Some(fixtures) = &self.fixtures
+            && fixtures.mode == FixtureMode::Record
+            && status.is_success()
+        {
+            let path = fixtures.next_path();
+            std::fs::write(&path, &body).with_context(|| format!("Failed to record response to {}", path.display()))?;
+        }
+        Ok((status, retry_after, body))
+    }
+
+    /// Turn access denials and HTTP errors into errors, passing successful bodies through
+    fn check_response(status: reqwest::StatusCode, body: String) -> Result<String> {
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(AuthExpired { detail: body }.into());
+        }
+
+        if status == reqwest::StatusCode::FORBIDDEN && body.contains("not accessible by") {
+            return Err(ProjectAccessError {
+                owner: None,
+                detail: body,
+            }
+            .into());
+        }
+
+        if !status.is_success() {
+            return Err(anyhow!("GitHub API error ({}): {}", status, body));
+        }
+
+        Ok(body)
+    }
+
+    /// Fetch all matching issues of a project, following pagination
+    pub async fn fetch_project_issues(
+        &self,
+        project_node_id: &str,
+        filters: &IssueFilters,
+        collect_stats: bool,
+    ) -> Result<(Vec<Issue>, FetchStats)> {
+        let mut stats = FetchStats::default();
+        let issues = self
+            .fetch_project_issues_stream(project_node_id, filters, collect_stats, &mut stats)
+            .try_collect()
+            .await?;
+        Ok((issues, stats))
+    }
+
+    /// Stream matching issues as their pages arrive instead of buffering the whole board.
+    /// `stats` is updated as pages are processed and is complete once the stream ends.
+    pub fn fetch_project_issues_stream<'a>(
+        &'a self,
+        project_node_id: &'a str,
+        filters: &'a IssueFilters,
+        collect_stats: bool,
+        stats: &'a mut FetchStats,
+    ) -> impl Stream<Item = Result<Issue>> + 'a {
+        struct State<'a> {
+            cursor: Option<String>,
+            pending: VecDeque<Issue>,
+            seen_ids: HashSet<String>,
+            /// Issues kept so far, counted against `filters.sample`
+            kept: usize,
+            done: bool,
+            pages: usize,
+            /// Shrinks for the rest of the run when a page exceeds the node limit
+            page_size: Option<u32>,
+            date_source: DateSource,
+            stats: &'a mut FetchStats,
+        }
+
+        let state = State {
+            cursor: None,
+            pending: VecDeque::new(),
+            seen_ids: HashSet::new(),
+            kept: 0,
+            done: false,
+            pages: 0,
+            page_size: None,
+            date_source: filters.date_source(),
+            stats,
+        };
+
+        stream::try_unfold(state, move |mut state| async move {
+            loop {
+                if let Some(issue) = state.pending.pop_front() {
+                    return Ok(Some((issue, state)));
+                }
+                if state.done {
+                    // Every item landing in the wrong column usually means the field name is wrong
+                    if state.stats.total_items > state.stats.archived && state.stats.with_status == 0 {
+                        return Err(self.missing_status_field_error(project_node_id, &filters.status_field()).await);
+                    }
+                    // Guard against pagination silently dropping pages; a sample stops early on purpose
+                    if !state.stats.sampled && state.stats.total_items != state.stats.reported_total {
+                        eprintln!(
+                            "Warning: fetched {} item(s) but the project reports {}. Results may be incomplete.",
+                            state.stats.total_items, state.stats.reported_total
+                        );
+                    }
+                    if self.verbose {
+                        eprintln!(
+                            "Verbose: total query cost: {} point(s) over {} page(s)",
+                            state.stats.query_cost, state.pages
+                        );
+                    }
+                    return Ok(None);
+                }
+
+                let page_size = match state.page_size {
+                    Some(size) => size,
+                    None => page_size()?,
+                };
+                let page = self
+                    .fetch_project_items_page(
+                        project_node_id,
+                        filters,
+                        state.cursor.as_deref(),
+                        page_size,
+                        collect_stats,
+                    )
+                    .await;
+                let (issues, page_info, page_stats) = match page {
+                    Ok(page) => page,
+                    // Retry the same page with half as many items
+                    Err(e) if page_size > 1 && e.downcast_ref::<NodeLimitError>().is_some() => {
+                        state.page_size = Some(page_size / 2);
+                        if self.verbose {
+                            eprintln!(
+                                "Verbose: page of {} item(s) exceeded GitHub's node limit, retrying with {}",
+                                page_size,
+                                page_size / 2
+                            );
+                        }
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
+                state.page_size = Some(page_size);
+
+                state.pages += 1;
+                let stats = &mut *state.stats;
+                stats.query_cost += page_stats.query_cost;
+                stats.with_status += page_stats.with_status;
+                stats.total_items += page_stats.total_items;
+                stats.reported_total = page_stats.reported_total;
+                stats.archived += page_stats.archived;
+                stats.archived_items.extend(page_stats.archived_items);
+                stats.skipped_active += page_stats.skipped_active;
+                stats.wrong_column += page_stats.wrong_column;
+                stats.not_issue += page_stats.not_issue;
+                stats.columns_seen.extend(page_stats.columns_seen);
+                stats.iterations_seen.extend(page_stats.iterations_seen);
+
+                let sample_reached = |kept: usize| filters.sample.is_some_and(|n| kept >= n);
+                let mut items = issues.into_iter();
+                for (issue, iteration_match) in items.by_ref() {
+                    if keep_issue(&issue, iteration_match, filters, &state.date_source, &mut state.seen_ids, stats) {
+                        state.pending.push_back(issue);
+                        state.kept += 1;
+                        if sample_reached(state.kept) {
+                            break;
+                        }
+                    }
+                }
+
+                let sampled = sample_reached(state.kept);
+                stats.sampled |= sampled && (!items.as_slice().is_empty() || page_info.has_next_page);
+                state.done = sampled || !page_info.has_next_page;
+                state.cursor = page_info.end_cursor;
+            }
+        })
+    }
+
+    /// Variable declarations and aliased selections fetching each custom field in the same query,
+    /// e.g. `field0: fieldValueByName(name: $field0) { ... }`
+    fn custom_field_selection(fields: &[String]) -> (String, String) {
+        let mut variables = String::new();
+        let mut selections = String::new();
+        for i in 0..fields.len() {
+            variables.push_str(&format!(", $field{}: String!", i));
+            selections.push_str(&format!(
+                "field{i}: fieldValueByName(name: $field{i}) {{ __typename ... on ProjectV2ItemFieldSingleSelectValue {{ name }} }}\n"
+            ));
+        }
+        (variables, selections)
+    }
+
+    async fn fetch_project_items_page(
+        &self,
+        project_node_id: &str,
+        filters: &IssueFilters,
+        cursor: Option<&str>,
+        page_size: u32,
+        collect_stats: bool,
+    ) -> Result<(Vec<(Issue, Option<bool>)>, PageInfo, FetchStats)> {
+        let query = r#"
+            query($projectId: ID!, $cursor: String, $pageSize: Int!, $statusField: String!, $iterationField: String!, $dateField: String!, $withDate: Boolean!, $withCost: Boolean!, $withLinks: Boolean!__FIELD_VARIABLES__) {
+                rateLimit @include(if: $withCost) {
+                    cost
+                    remaining
+                }
+                node(id: $projectId) {
+                    ... on ProjectV2 {
+                        items(first: $pageSize, after: $cursor) {
+                            totalCount
+                            pageInfo {
+                                hasNextPage
+                                endCursor
+                            }
+                            nodes {
+                                id
+                                isArchived
+                                fieldValueByName(name: $statusField) {
+                                    ... on ProjectV2ItemFieldSingleSelectValue {
+                                        __typename
+                                        name
+                                    }
+                                }
+                                iteration: fieldValueByName(name: $iterationField) {
+                                    ... on ProjectV2ItemFieldIterationValue {
+                                        __typename
+                                        title
+                                        startDate
+                                    }
+                                }
+                                dateValue: fieldValueByName(name: $dateField) @include(if: $withDate) {
+                                    __typename
+                                    ... on ProjectV2ItemFieldDateValue {
+                                        date
+                                    }
+                                }
+                                __FIELD_SELECTIONS__
+                                content {
+                                    __typename
+                                    ... on DraftIssue {
+                                        id
+                                        title
+                                        body
+                                    }
+                                    ... on Issue {
+                                        id
+                                        number
+                                        title
+                                        url
+                                        createdAt
+                                        closedAt
+                                        stateReason
+                                        updatedAt
+                                        repository {
+                                            nameWithOwner
+                                        }
+                                        author {
+                                            __typename
+                                            login
+                                        }
+                                        parent {
+                                            number
+                                            title
+                                            url
+                                        }
+                                        labels(first: 20) {
+                                            nodes {
+                                                name
+                                            }
+                                        }
+                                        assignees(first: 10) {
+                                            nodes {
+                                                login
+                                            }
+                                        }
+                                        comments(last: 1) {
+                                            totalCount
+                                            nodes {
+                                                createdAt
+                                            }
+                                        }
+                                        milestone {
+                                            title
+                                            dueOn
+                                        }
+                                        timelineItems(itemTypes: [CLOSED_EVENT], last: 1) {
+                                            nodes {
+                                                ... on ClosedEvent {
+                                                    actor {
+                                                        login
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        trackedInIssues(first: 10) @include(if: $withLinks) {
+                                            nodes {
+                                                id
+                                            }
+                                        }
+                                        duplicates: timelineItems(itemTypes: [MARKED_AS_DUPLICATE_EVENT], last: 10) @include(if: $withLinks) {
+                                            nodes {
+                                                ... on MarkedAsDuplicateEvent {
+                                                    canonical {
+                                                        ... on Issue {
+                                                            id
+                                                        }
+                                                    }
+                                                    duplicate {
+                                                        ... on Issue {
+                                                            id
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+        let (field_variables, field_selections) = Self::custom_field_selection(&filters.fields);
+        let query = query
+            .replace("__FIELD_VARIABLES__", &field_variables)
+            .replace("__FIELD_SELECTIONS__", &field_selections);
+
+        // Field names come from --status-field/--iteration-field or the environment
+        let status_field = filters.status_field();
+        let iteration_field = filters.iteration_field();
+        let date_source = filters.date_source();
+        let date_field = date_source.field_name();
+
+        let mut variables = json!({
+            "projectId": project_node_id,
+            "cursor": cursor,
+            "pageSize": page_size,
+            "statusField": status_field,
+            "iterationField": iteration_field,
+            "dateField": date_field.unwrap_or_default(),
+            "withDate": date_field.is_some(),
+            "withCost": self.verbose,
+            "withLinks": filters.include_links
+        });
+        for (i, field) in filters.fields.iter().enumerate() {
+            variables[format!("field{}", i)] = json!(field);
+        }
+
+        let response = self.execute_query(&query, &variables).await?;
+
+        let parsed: GraphQLResponse<ProjectData> =
+            serde_json::from_str(&response).context("Failed to parse GitHub response")?;
+
+        if let Some(errors) = parsed.errors {
+            if is_node_limit_error(&errors) {
+                let detail = errors.iter().map(|e| e.message.as_str()).collect::<Vec<_>>().join(", ");
+                return Err(NodeLimitError { detail }.into());
+            }
+            return Err(self.node_error(&errors, project_node_id));
+        }
 
+        let data = parsed.data.ok_or_else(|| self.project_not_found(project_node_id))?;
+        let project = data
+            .node
+            .ok_or_else(|| self.project_not_found(project_node_id))?;
+
+        let mut stats = FetchStats {
+            total_items: project.items.nodes.len(),
+            reported_total: project.items.total_count,
+            ..Default::default()
+        };
+
+        if let Some(rate_limit) = data.rate_limit {
+            stats.query_cost = rate_limit.cost;
+            eprintln!(
+                "Verbose: page of {} item(s) cost {} point(s) ({} remaining)",
+                stats.total_items, rate_limit.cost, rate_limit.remaining
+            );
+        }
+
+        let today = crate::iteration_filter::today();
+        let mut issues = Vec::new();
+
+        for item in project.items.nodes {
             // Check if item is in the specified column
             let item_column = item
                 .field_value_by_name
                 .as_ref()
                 .and_then(|fv| fv.name());
+            if item_column.is_some() && !item.is_archived {
+                stats.with_status += 1;
+            }
+
+            // Archived items are hidden in the GitHub UI and skipped unless requested
+            if item.is_archived {
+                stats.archived += 1;
+            }
+            if !filters.archived.keeps(item.is_archived) {
+                if !item.is_archived {
+                    stats.skipped_active += 1;
+                } else if filters.list_archived {
+                    let (title, url) = match &item.content {
+                        Some(ItemContent::Issue(content)) => (content.title.clone(), Some(content.url.clone())),
+                        Some(ItemContent::DraftIssue(draft)) => (draft.title.clone(), None),
+                        _ => ("(pull request or redacted item)".to_string(), None),
+                    };
+                    stats.archived_items.push(ArchivedItem {
+                        title,
+                        url,
+                        column: item_column.map(str::to_string),
+                    });
+                }
+                continue;
+            }
 
             // Collect column names for debug output
             if collect_stats {
@@ -460,7 +1826,7 @@ impl GitHubClient {
                 }
             }
 
-            if item_column != Some(column_name) {
+            if item_column != Some(filters.column.as_str()) {
                 stats.wrong_column += 1;
                 continue;
             }
@@ -478,31 +1844,84 @@ impl GitHubClient {
                 }
             }
 
-            // Filter by iteration if specified
-            if let Some(filter) = iteration_filter {
-                if !matches_iteration_filter(item_iteration, item_iteration_start, filter) {
-                    stats.filtered_by_iteration += 1;
-                    continue;
-                }
-            }
+            // Evaluated here while the iteration is at hand; combined with the time filter later
+            let iteration_match = filters
+                .iteration
+                .as_ref()
+                .map(|filter| matches_iteration_filter(item_iteration, item_iteration_start, filter, today));
+
+            let fields: HashMap<String, String> = filters
+                .fields
+                .iter()
+                .enumerate()
+                .filter_map(|(i, field)| {
+                    let value = item.custom_fields.get(&format!("field{}", i))?.as_ref()?.name()?;
+                    Some((field.clone(), value.to_string()))
+                })
+                .collect();
+            let project_date = item.date_value.as_ref().and_then(|value| value.date());
 
             // Extract issue content
             match item.content {
                 Some(ItemContent::Issue(content)) => {
+                    let linked_ids = content.linked_ids();
+                    let (comment_count, last_comment_at) = content
+                        .comments
+                        .map(|c| (c.total_count, c.nodes.last().map(|comment| comment.created_at)))
+                        .unwrap_or_default();
                     let parent = content.parent.map(|p| crate::models::ParentIssue {
                         number: p.number,
                         title: p.title,
                         url: p.url,
                     });
 
-                    issues.push(Issue {
+                    let issue = Issue {
+                        id: content.id,
                         number: content.number,
                         title: content.title,
                         url: content.url,
+                        created_at: content.created_at,
                         closed_at: content.closed_at,
+                        state_reason: content.state_reason,
+                        updated_at: content.updated_at,
                         repository: content.repository.name_with_owner,
                         parent,
-                    });
+                        labels: content
+                            .labels
+                            .map(|l| l.nodes.into_iter().map(|label| label.name).collect())
+                            .unwrap_or_default(),
+                        author: content.author.map(Actor::display_login),
+                        closed_by: content
+                            .timeline_items
+                            .and_then(|t| t.nodes.into_iter().last())
+                            .and_then(|event| event.actor)
+                            .map(|actor| actor.login),
+                        assignees: content
+                            .assignees
+                            .map(|a| a.nodes.into_iter().map(|actor| actor.login).collect())
+                            .unwrap_or_default(),
+                        comment_count,
+                        last_comment_at,
+                        milestone_due_on: content.milestone.as_ref().and_then(|m| m.due_on),
+                        milestone: content.milestone.map(|m| m.title),
+                        project_date,
+                        is_draft: false,
+                        linked_ids,
+                        merged: Vec::new(),
+                        fields,
+                    };
+                    issues.push((issue, iteration_match));
+                }
+                Some(ItemContent::DraftIssue(draft)) if filters.include_drafts => {
+                    let draft = Issue {
+                        id: draft.id,
+                        title: draft.title,
+                        is_draft: true,
+                        project_date,
+                        fields,
+                        ..Default::default()
+                    };
+                    issues.push((draft, iteration_match));
                 }
                 _ => {
                     stats.not_issue += 1;
@@ -513,3 +1932,1377 @@ impl GitHubClient {
         Ok((issues, project.items.page_info, stats))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use wiremock::matchers::{body_partial_json, body_string_contains, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mock_client() -> (MockServer, GitHubClient) {
+        let server = MockServer::start().await;
+        let client = GitHubClient::with_graphql_url("test-token", &server.uri()).unwrap();
+        (server, client)
+    }
+
+    fn issue_item(number: u64, status: &str, iteration: Option<&str>) -> Value {
+        json!({
+            "id": format!("PVTI_{}", number),
+            "isArchived": false,
+            "fieldValueByName": {
+                "__typename": "ProjectV2ItemFieldSingleSelectValue",
+                "name": status
+            },
+            "iteration": iteration.map(|title| json!({
+                "__typename": "ProjectV2ItemFieldIterationValue",
+                "title": title,
+                "startDate": "2024-01-01"
+            })),
+            "content": {
+                "__typename": "Issue",
+                "id": format!("I_{}", number),
+                "number": number,
+                "title": format!("Issue {}", number),
+                "url": format!("https://github.com/myorg/repo/issues/{}", number),
+                "closedAt": "2024-01-15T14:30:00Z",
+                "repository": { "nameWithOwner": "myorg/repo" },
+                "parent": null,
+                "labels": { "nodes": [] },
+                "timelineItems": { "nodes": [] }
+            }
+        })
+    }
+
+    fn items_page(nodes: Vec<Value>, total: usize, next_cursor: Option<&str>) -> Value {
+        json!({
+            "data": {
+                "node": {
+                    "items": {
+                        "totalCount": total,
+                        "pageInfo": {
+                            "hasNextPage": next_cursor.is_some(),
+                            "endCursor": next_cursor
+                        },
+                        "nodes": nodes
+                    }
+                }
+            }
+        })
+    }
+
+    fn done_filters() -> IssueFilters {
+        IssueFilters {
+            column: "Done".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_paginates_across_pages() {
+        let (server, client) = mock_client().await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "cursor": null } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(1, "Done", None), issue_item(2, "Done", None)],
+                3,
+                Some("cursor-1"),
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "cursor": "cursor-1" } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(3, "Done", None)],
+                3,
+                None,
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (issues, stats) = client
+            .fetch_project_issues("PVT_test", &done_filters(), false)
+            .await
+            .unwrap();
+
+        let numbers: Vec<u64> = issues.iter().map(|i| i.number).collect();
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(stats.total_items, 3);
+        assert_eq!(stats.reported_total, 3);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_sample_stops_paginating() {
+        let (server, client) = mock_client().await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "cursor": null } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(1, "Todo", None), issue_item(2, "Done", None), issue_item(3, "Done", None)],
+                6,
+                Some("cursor-1"),
+            )))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "cursor": "cursor-1" } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(4, "Done", None), issue_item(5, "Done", None), issue_item(6, "Done", None)],
+                6,
+                None,
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // The first page holds the sample, so the second is never requested
+        let filters = IssueFilters {
+            sample: Some(2),
+            ..done_filters()
+        };
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![2, 3]);
+        assert!(stats.sampled);
+
+        // A sample larger than the board reads everything and isn't flagged
+        let filters = IssueFilters {
+            sample: Some(10),
+            ..done_filters()
+        };
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues.len(), 5);
+        assert!(!stats.sampled);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_halves_page_size_on_node_limit() {
+        let (server, client) = mock_client().await;
+
+        let node_limit = json!({
+            "errors": [{
+                "type": "MAX_NODE_LIMIT_EXCEEDED",
+                "message": "This query requests up to 1,010,100 possible nodes which exceeds the maximum limit of 500,000."
+            }]
+        });
+        for size in [100, 50] {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({ "variables": { "pageSize": size } })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(node_limit.clone()))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        // The smaller size sticks for the following pages
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "pageSize": 25, "cursor": null } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(1, "Done", None)],
+                2,
+                Some("cursor-1"),
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "pageSize": 25, "cursor": "cursor-1" } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(2, "Done", None)],
+                2,
+                None,
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (issues, _) = client.fetch_project_issues("PVT_test", &done_filters(), false).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_title_filters() {
+        let (server, client) = mock_client().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![
+                    issue_item(1, "Done", None),
+                    issue_item(2, "Done", None),
+                    issue_item(13, "Done", None),
+                ],
+                3,
+                None,
+            )))
+            .mount(&server)
+            .await;
+
+        let filters = IssueFilters {
+            title_match: Some(parse_title_regex(r"^Issue \d$", "--title-match").unwrap()),
+            title_exclude: Some(parse_title_regex("2", "--title-exclude").unwrap()),
+            ..done_filters()
+        };
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+
+        let numbers: Vec<u64> = issues.iter().map(|i| i.number).collect();
+        assert_eq!(numbers, vec![1]);
+        assert_eq!(stats.filtered_by_title, 2);
+    }
+
+    #[test]
+    fn test_parse_title_regex_error() {
+        let err = parse_title_regex("[infra", "--title-match").unwrap_err();
+        assert!(err.to_string().starts_with("Invalid --title-match pattern '[infra'"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_linked_ids() {
+        let (server, client) = mock_client().await;
+
+        let mut item = issue_item(1, "Done", None);
+        item["content"]["trackedInIssues"] = json!({ "nodes": [{ "id": "I_9" }] });
+        item["content"]["duplicates"] = json!({ "nodes": [
+            { "canonical": { "id": "I_5" }, "duplicate": { "id": "I_1" } },
+            { "canonical": {}, "duplicate": { "id": "I_1" } }
+        ] });
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "withLinks": true } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(vec![item], 1, None)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let filters = IssueFilters {
+            include_links: true,
+            ..done_filters()
+        };
+        let (issues, _) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues[0].linked_ids, vec!["I_5", "I_9"]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_custom_fields() {
+        let (server, client) = mock_client().await;
+
+        let mut with_values = issue_item(1, "Done", None);
+        with_values["field0"] = json!({ "__typename": "ProjectV2ItemFieldSingleSelectValue", "name": "Frontend" });
+        with_values["field1"] = json!({ "__typename": "ProjectV2ItemFieldSingleSelectValue", "name": "High" });
+        let mut without_values = issue_item(2, "Done", None);
+        without_values["field0"] = json!({ "__typename": "ProjectV2ItemFieldTextValue" });
+        without_values["field1"] = serde_json::Value::Null;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "field0": "Area", "field1": "Priority" } })))
+            .and(body_string_contains("field1: fieldValueByName(name: $field1)"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(items_page(vec![with_values, without_values], 2, None)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let filters = IssueFilters {
+            fields: vec!["Area".to_string(), "Priority".to_string()],
+            ..done_filters()
+        };
+        let (issues, _) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues[0].fields.get("Area").map(String::as_str), Some("Frontend"));
+        assert_eq!(issues[0].fields.get("Priority").map(String::as_str), Some("High"));
+        assert!(issues[1].fields.is_empty());
+    }
+
+    #[test]
+    fn test_custom_field_selection() {
+        let (variables, selections) = GitHubClient::custom_field_selection(&[]);
+        assert!(variables.is_empty() && selections.is_empty());
+
+        let fields = vec!["Area".to_string(), "Team".to_string()];
+        let (variables, selections) = GitHubClient::custom_field_selection(&fields);
+        assert_eq!(variables, ", $field0: String!, $field1: String!");
+        assert!(selections.contains("field0: fieldValueByName(name: $field0)"));
+        assert!(selections.contains("field1: fieldValueByName(name: $field1)"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_comment_count() {
+        let (server, client) = mock_client().await;
+
+        let mut item = issue_item(1, "Done", None);
+        item["content"]["comments"] = json!({ "totalCount": 7, "nodes": [{ "createdAt": "2024-10-15T09:30:00Z" }] });
+        Mock::given(method("POST"))
+            .and(body_string_contains("comments(last: 1)"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(vec![item, issue_item(2, "Done", None)], 2, None)))
+            .mount(&server)
+            .await;
+
+        let (issues, _) = client.fetch_project_issues("PVT_test", &done_filters(), false).await.unwrap();
+        assert_eq!(issues[0].comment_count, 7);
+        assert_eq!(issues[0].last_comment_at.map(|t| t.to_rfc3339()).as_deref(), Some("2024-10-15T09:30:00+00:00"));
+        assert_eq!(issues[1].comment_count, 0);
+        assert!(issues[1].last_comment_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_draft_release() {
+        let (server, client) = mock_client().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/myorg/repo/releases"))
+            .and(body_partial_json(json!({ "tag_name": "v1.2.0", "draft": true, "body": "notes" })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+                "html_url": "https://github.com/myorg/repo/releases/tag/untagged-1"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url = client.create_draft_release("myorg/repo", "v1.2.0", "notes").await.unwrap();
+        assert_eq!(url, "https://github.com/myorg/repo/releases/tag/untagged-1");
+    }
+
+    #[tokio::test]
+    async fn test_create_draft_release_error() {
+        let (server, client) = mock_client().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(422).set_body_string(r#"{"message":"Validation Failed"}"#))
+            .mount(&server)
+            .await;
+
+        let err = client.create_draft_release("myorg/repo", "v1.2.0", "notes").await.unwrap_err().to_string();
+        assert!(err.contains("Failed to create release v1.2.0 in myorg/repo (HTTP 422"));
+        assert!(err.contains("Validation Failed"));
+    }
+
+    #[tokio::test]
+    async fn test_tag_date() {
+        let (server, client) = mock_client().await;
+        let tag_response = |tag: &str, body: Value| {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({ "variables": { "owner": "myorg", "name": "repo", "tag": tag } })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": { "repository": body } })))
+        };
+        tag_response("v1.2.0", json!({
+            "release": { "publishedAt": "2024-10-01T09:00:00Z" },
+            "ref": { "target": { "committedDate": "2024-09-30T17:00:00Z" } }
+        }))
+        .mount(&server)
+        .await;
+        // Annotated tag without a release: the Tag object wraps the commit
+        tag_response("v1.1.0", json!({
+            "release": null,
+            "ref": { "target": { "target": { "committedDate": "2024-08-15T12:00:00Z" } } }
+        }))
+        .mount(&server)
+        .await;
+        tag_response("v9.9.9", json!({ "release": null, "ref": null })).mount(&server).await;
+
+        let released = client.tag_date("myorg/repo", "v1.2.0").await.unwrap();
+        assert_eq!(released, "2024-10-01T09:00:00Z".parse::<DateTime<Utc>>().unwrap());
+        let tagged = client.tag_date("myorg/repo", "v1.1.0").await.unwrap();
+        assert_eq!(tagged, "2024-08-15T12:00:00Z".parse::<DateTime<Utc>>().unwrap());
+
+        let err = client.tag_date("myorg/repo", "v9.9.9").await.unwrap_err().to_string();
+        assert!(err.contains("Tag 'v9.9.9' not found in myorg/repo"), "{}", err);
+        assert!(client.tag_date("myorg", "v1.2.0").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_missing_status_field_lists_fields() {
+        let (server, client) = mock_client().await;
+
+        let mut item = issue_item(1, "Done", None);
+        item["fieldValueByName"] = Value::Null;
+        Mock::given(method("POST"))
+            .and(body_string_contains("items(first"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(vec![item], 1, None)))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("fields(first"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "node": { "fields": { "nodes": [{ "name": "Title" }, { "name": "State" }, {}] } } }
+            })))
+            .mount(&server)
+            .await;
+
+        let err = client
+            .fetch_project_issues("PVT_test", &done_filters(), false)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("No item in the project has a value for the \"Status\" field"));
+        assert!(err.contains("Available fields: Title, State"));
+    }
+
+    #[tokio::test]
+    async fn test_secondary_rate_limit_retries() {
+        let (server, mut client) = mock_client().await;
+        client.secondary_limit_delay = Duration::from_millis(10);
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "errors": [{ "type": "RATE_LIMITED", "message": "You have exceeded a secondary rate limit." }]
+            })))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(vec![issue_item(1, "Done", None)], 1, None)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (issues, _) = client.fetch_project_issues("PVT_test", &done_filters(), false).await.unwrap();
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_secondary_rate_limit_gives_up() {
+        let (server, mut client) = mock_client().await;
+        client.secondary_limit_delay = Duration::from_millis(1);
+
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(403).set_body_string(r#"{"message":"You have exceeded a secondary rate limit"}"#),
+            )
+            .expect(u64::from(SECONDARY_LIMIT_RETRIES) + 1)
+            .mount(&server)
+            .await;
+
+        let err = client.fetch_project_issues("PVT_test", &done_filters(), false).await.unwrap_err();
+        assert!(err.to_string().contains("secondary rate limit"));
+    }
+
+    #[test]
+    fn test_is_secondary_rate_limit() {
+        use reqwest::StatusCode;
+
+        let secondary = r#"{"errors":[{"type":"RATE_LIMITED","message":"was submitted too quickly"}]}"#;
+        assert!(is_secondary_rate_limit(StatusCode::OK, secondary));
+        let primary = r#"{"errors":[{"type":"RATE_LIMITED","message":"API rate limit exceeded for user ID 1."}]}"#;
+        assert!(!is_secondary_rate_limit(StatusCode::OK, primary));
+        assert!(is_secondary_rate_limit(
+            StatusCode::FORBIDDEN,
+            r#"{"message":"You have exceeded a secondary rate limit."}"#
+        ));
+        assert!(!is_secondary_rate_limit(StatusCode::FORBIDDEN, r#"{"message":"Bad credentials"}"#));
+        assert!(!is_secondary_rate_limit(StatusCode::OK, r#"{"data":{}}"#));
+    }
+
+    #[tokio::test]
+    async fn test_verbose_tracks_query_cost() {
+        let server = MockServer::start().await;
+        let client = GitHubClient::with_graphql_url("test-token", &server.uri())
+            .unwrap()
+            .verbose(true);
+
+        let page = |cursor: Option<&str>, next: Option<&str>, remaining: u32| {
+            let mut body = items_page(vec![issue_item(1, "Done", None)], 2, next);
+            body["data"]["rateLimit"] = json!({ "cost": 1, "remaining": remaining });
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({ "variables": { "cursor": cursor, "withCost": true } })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .expect(1)
+        };
+        page(None, Some("cursor-1"), 4999).mount(&server).await;
+        page(Some("cursor-1"), None, 4998).mount(&server).await;
+
+        let (_, stats) = client
+            .fetch_project_issues("PVT_test", &done_filters(), false)
+            .await
+            .unwrap();
+
+        assert_eq!(stats.query_cost, 2);
+    }
+
+    #[tokio::test]
+    async fn test_stream_yields_issues_per_page() {
+        let (server, client) = mock_client().await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "cursor": null } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(1, "Done", None), issue_item(2, "In Progress", None)],
+                3,
+                Some("cursor-1"),
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "cursor": "cursor-1" } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(3, "Done", None)],
+                3,
+                None,
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let filters = done_filters();
+        let mut stats = FetchStats::default();
+        let mut stream = Box::pin(client.fetch_project_issues_stream("PVT_test", &filters, false, &mut stats));
+
+        // The first issue is available before the second page is requested
+        let first = stream.try_next().await.unwrap().unwrap();
+        assert_eq!(first.number, 1);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        let rest: Vec<Issue> = stream.try_collect().await.unwrap();
+        assert_eq!(rest.iter().map(|i| i.number).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(stats.total_items, 3);
+        assert_eq!(stats.wrong_column, 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_dedupes_by_node_id() {
+        let (server, client) = mock_client().await;
+
+        // The same issue, seen again after being transferred to another repository
+        let mut transferred = issue_item(9, "Done", None);
+        transferred["content"]["id"] = json!("I_2");
+        transferred["content"]["repository"]["nameWithOwner"] = json!("myorg/other");
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "cursor": null } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(1, "Done", None), issue_item(2, "Done", None)],
+                3,
+                Some("cursor-1"),
+            )))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "cursor": "cursor-1" } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(vec![transferred], 3, None)))
+            .mount(&server)
+            .await;
+
+        let (issues, stats) = client
+            .fetch_project_issues("PVT_test", &done_filters(), false)
+            .await
+            .unwrap();
+
+        let ids: Vec<&str> = issues.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["I_1", "I_2"]);
+        assert_eq!(stats.duplicates, 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_surfaces_graphql_errors() {
+        let (server, client) = mock_client().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": null,
+                "errors": [{ "message": "Something went wrong" }]
+            })))
+            .mount(&server)
+            .await;
+
+        let err = client
+            .fetch_project_issues("PVT_test", &done_filters(), false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Something went wrong"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_owner_projects_skips_closed() {
+        let (server, client) = mock_client().await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "owner": "myorg" } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {
+                    "repositoryOwner": {
+                        "projectsV2": {
+                            "nodes": [
+                                { "number": 7, "title": "Roadmap", "closed": false },
+                                { "number": 2, "title": "Old board", "closed": true },
+                                { "number": 5, "title": "Sprint board", "closed": false }
+                            ]
+                        }
+                    }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let projects = client.fetch_owner_projects("myorg").await.unwrap();
+        let numbers: Vec<u32> = projects.iter().map(|p| p.number).collect();
+        assert_eq!(numbers, vec![5, 7]);
+        assert_eq!(projects[0].title, "Sprint board");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_project_falls_back_to_user() {
+        let (server, client) = mock_client().await;
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("organization(login"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "organization": null },
+                "errors": [{ "message": "Could not resolve to an Organization with the login of 'octocat'." }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("user(login"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {
+                    "user": {
+                        "projectV2": {
+                            "id": "PVT_user",
+                            "url": "https://github.com/users/octocat/projects/3"
+                        }
+                    }
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let project = client.resolve_project_id("octocat/3").await.unwrap();
+        assert_eq!(project.node_id, "PVT_user");
+        assert_eq!(project.url.as_deref(), Some("https://github.com/users/octocat/projects/3"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_project_reports_missing_project_permission() {
+        let (server, client) = mock_client().await;
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("organization(login"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "organization": { "projectV2": null } },
+                "errors": [{
+                    "type": "FORBIDDEN",
+                    "message": "Resource not accessible by personal access token"
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // No point retrying as a user project when the token lacks access
+        Mock::given(method("POST"))
+            .and(body_string_contains("user(login"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let err = client.resolve_project_id("myorg/5").await.unwrap_err();
+        assert!(err.is::<ProjectAccessError>());
+        let message = err.to_string();
+        assert!(message.contains("\"Projects: read\" permission on 'myorg'"));
+        assert!(message.contains("Resource not accessible by personal access token"));
+    }
+
+    #[tokio::test]
+    async fn test_errors_redact_token() {
+        let (server, client) = mock_client().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401).set_body_string(
+                "Bad credentials for test-token (Authorization: Bearer test-token)",
+            ))
+            .mount(&server)
+            .await;
+
+        let err = client
+            .fetch_project_issues("PVT_test", &done_filters(), false)
+            .await
+            .unwrap_err();
+        assert!(err.is::<AuthExpired>());
+        let message = format!("{:#}", err);
+        assert!(!message.contains("test-token"), "token leaked: {}", message);
+        assert!(message.contains("Bad credentials for *** (Authorization: Bearer ***)"));
+    }
+
+    #[tokio::test]
+    async fn test_reauth_resumes_pagination() {
+        let (server, client) = mock_client().await;
+        let client = client.on_auth_expired(Box::new(|| Ok(Some("fresh-token".to_string()))));
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "cursor": null } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(1, "Done", None)],
+                2,
+                Some("cursor-1"),
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // The token expires before the second page
+        Mock::given(method("POST"))
+            .and(header("Authorization", "Bearer test-token"))
+            .and(body_partial_json(json!({ "variables": { "cursor": "cursor-1" } })))
+            .respond_with(ResponseTemplate::new(401).set_body_string(r#"{"message":"Bad credentials"}"#))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(header("Authorization", "Bearer fresh-token"))
+            .and(body_partial_json(json!({ "variables": { "cursor": "cursor-1" } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(2, "Done", None)],
+                2,
+                None,
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (issues, _) = client.fetch_project_issues("PVT_test", &done_filters(), false).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_reauth_declined() {
+        let (server, client) = mock_client().await;
+        let client = client.on_auth_expired(Box::new(|| Ok(None)));
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401).set_body_string(r#"{"message":"Bad credentials"}"#))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let err = client.fetch_project_issues("PVT_test", &done_filters(), false).await.unwrap_err();
+        assert!(err.is::<AuthExpired>());
+        assert!(err.to_string().contains("doner auth login"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_reports_forbidden_status() {
+        let (server, client) = mock_client().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+                "message": "Resource not accessible by personal access token"
+            })))
+            .mount(&server)
+            .await;
+
+        let err = client
+            .fetch_project_issues("PVT_test", &done_filters(), false)
+            .await
+            .unwrap_err();
+        assert!(err.is::<ProjectAccessError>());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_stats_column_and_iteration_filtering() {
+        let (server, client) = mock_client().await;
+
+        let mut archived = issue_item(5, "Done", Some("Sprint 2"));
+        archived["isArchived"] = json!(true);
+        let mut draft = issue_item(6, "Done", Some("Sprint 2"));
+        draft["content"] = json!({ "__typename": "DraftIssue", "id": "DI_6", "title": "Idea", "body": null });
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![
+                    issue_item(1, "Done", Some("Sprint 2")),
+                    issue_item(2, "Done", Some("Sprint 1")),
+                    issue_item(3, "In Progress", Some("Sprint 2")),
+                    issue_item(4, "Done", None),
+                    archived,
+                    draft,
+                ],
+                6,
+                None,
+            )))
+            .mount(&server)
+            .await;
+
+        let filters = IssueFilters {
+            iteration: Some("Sprint 2".to_string()),
+            ..done_filters()
+        };
+        let (issues, stats) = client
+            .fetch_project_issues("PVT_test", &filters, true)
+            .await
+            .unwrap();
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].number, 1);
+        assert_eq!(stats.total_items, 6);
+        assert_eq!(stats.archived, 1);
+        assert_eq!(stats.wrong_column, 1);
+        assert_eq!(stats.filtered_by_iteration, 2);
+        assert_eq!(stats.not_issue, 1);
+        assert!(stats.columns_seen.contains("In Progress"));
+        assert!(stats.iterations_seen.contains("<no iteration>"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_filter_mode_or() {
+        let (server, client) = mock_client().await;
+
+        let closed_on = |mut item: Value, date: &str| {
+            item["content"]["closedAt"] = json!(format!("{}T12:00:00Z", date));
+            item
+        };
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![
+                    // Right iteration, closed too early
+                    closed_on(issue_item(1, "Done", Some("Sprint 2")), "2024-01-01"),
+                    // Wrong iteration, closed recently
+                    closed_on(issue_item(2, "Done", Some("Sprint 1")), "2024-02-01"),
+                    // Neither
+                    closed_on(issue_item(3, "Done", Some("Sprint 1")), "2024-01-01"),
+                ],
+                3,
+                None,
+            )))
+            .mount(&server)
+            .await;
+
+        let mut filters = IssueFilters {
+            iteration: Some("Sprint 2".to_string()),
+            since: "2024-01-15T00:00:00Z".parse().ok(),
+            ..done_filters()
+        };
+
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert!(issues.is_empty());
+        assert_eq!(stats.filtered_by_iteration, 2);
+        assert_eq!(stats.filtered_by_time, 1);
+
+        filters.mode = FilterMode::Or;
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        let numbers: Vec<u64> = issues.iter().map(|i| i.number).collect();
+        assert_eq!(numbers, vec![1, 2]);
+        assert_eq!(stats.filtered_by_iteration, 1);
+        assert_eq!(stats.filtered_by_time, 0);
+    }
+
+    #[test]
+    fn test_filter_mode_combine() {
+        assert!(FilterMode::And.combine(None, None));
+        assert!(!FilterMode::Or.combine(Some(false), None));
+        assert!(FilterMode::Or.combine(None, Some(true)));
+        assert!(!FilterMode::And.combine(Some(true), Some(false)));
+        assert!(FilterMode::Or.combine(Some(true), Some(false)));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_include_drafts() {
+        let (server, client) = mock_client().await;
+
+        let mut draft = issue_item(6, "Done", None);
+        draft["content"] = json!({ "__typename": "DraftIssue", "id": "DI_6", "title": "Idea", "body": "notes" });
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(1, "Done", None), draft],
+                2,
+                None,
+            )))
+            .mount(&server)
+            .await;
+
+        // Drafts have no close date, so --since does not drop them
+        let filters = IssueFilters {
+            since: Some(Utc::now()),
+            include_drafts: true,
+            ..done_filters()
+        };
+        let (issues, stats) = client
+            .fetch_project_issues("PVT_test", &filters, false)
+            .await
+            .unwrap();
+
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].is_draft);
+        assert_eq!(issues[0].title, "Idea");
+        assert_eq!(stats.filtered_by_time, 1);
+        assert_eq!(stats.not_issue, 0);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_include_open() {
+        let (server, client) = mock_client().await;
+
+        // Dragged to Done while still open: no close date
+        let mut recent = issue_item(2, "Done", None);
+        recent["content"]["closedAt"] = Value::Null;
+        recent["content"]["createdAt"] = json!("2024-01-20T09:00:00Z");
+        let mut old = issue_item(3, "Done", None);
+        old["content"]["closedAt"] = Value::Null;
+        old["content"]["createdAt"] = json!("2023-06-01T09:00:00Z");
+        let mut undated = issue_item(4, "Done", None);
+        undated["content"]["closedAt"] = Value::Null;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(1, "Done", None), recent, old, undated],
+                4,
+                None,
+            )))
+            .mount(&server)
+            .await;
+
+        let since = "2024-01-10T00:00:00Z".parse().unwrap();
+        let filters = IssueFilters {
+            since: Some(since),
+            ..done_filters()
+        };
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![1]);
+        assert_eq!((stats.filtered_by_time, stats.missing_timestamp), (3, 3));
+
+        let filters = IssueFilters {
+            include_open: true,
+            ..filters
+        };
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!((stats.filtered_by_time, stats.missing_timestamp), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_active_since() {
+        let (server, client) = mock_client().await;
+
+        let mut active = issue_item(1, "Done", None);
+        active["content"]["updatedAt"] = json!("2024-02-01T10:00:00Z");
+        let mut stale = issue_item(2, "Done", None);
+        stale["content"]["updatedAt"] = json!("2024-01-15T14:30:00Z");
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("updatedAt"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![active, stale, issue_item(3, "Done", None)],
+                3,
+                None,
+            )))
+            .mount(&server)
+            .await;
+
+        let filters = IssueFilters {
+            since: Some("2024-01-01T00:00:00Z".parse().unwrap()),
+            active_since: Some("2024-01-20T00:00:00Z".parse().unwrap()),
+            ..done_filters()
+        };
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(stats.filtered_by_activity, 2);
+        assert_eq!(stats.filtered_by_time, 0);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_milestone_due_before() {
+        let (server, client) = mock_client().await;
+
+        let with_milestone = |number: u64, due_on: Option<&str>| {
+            let mut item = issue_item(number, "Done", None);
+            item["content"]["milestone"] = json!({ "title": "v1.0", "dueOn": due_on });
+            item
+        };
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("dueOn"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![
+                    with_milestone(1, Some("2024-11-15T00:00:00Z")),
+                    with_milestone(2, Some("2024-12-15T00:00:00Z")),
+                    with_milestone(3, None),
+                    issue_item(4, "Done", None),
+                ],
+                4,
+                None,
+            )))
+            .mount(&server)
+            .await;
+
+        let filters = IssueFilters {
+            milestone_due_before: Some("2024-12-01T00:00:00Z".parse().unwrap()),
+            ..done_filters()
+        };
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(issues[0].milestone.as_deref(), Some("v1.0"));
+        assert_eq!(stats.filtered_by_milestone_due, 3);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_completed_only() {
+        let (server, client) = mock_client().await;
+        let with_reason = |number, reason: Value| {
+            let mut item = issue_item(number, "Done", None);
+            item["content"]["stateReason"] = reason;
+            item
+        };
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("stateReason"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![
+                    with_reason(1, json!("COMPLETED")),
+                    with_reason(2, json!("NOT_PLANNED")),
+                    with_reason(3, json!("DUPLICATE")),
+                    with_reason(4, Value::Null),
+                ],
+                4,
+                None,
+            )))
+            .mount(&server)
+            .await;
+
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &done_filters(), false).await.unwrap();
+        assert_eq!(issues.len(), 4);
+        assert_eq!(issues[1].state_reason, Some(StateReason::NotPlanned));
+
+        let filters = IssueFilters {
+            completed_only: true,
+            ..done_filters()
+        };
+        let (issues, stats_completed) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![1]);
+        assert_eq!((stats.filtered_by_state_reason, stats_completed.filtered_by_state_reason), (0, 3));
+    }
+
+    #[test]
+    fn test_widen_window() {
+        let at = |s: &str| Some(DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc));
+        let mut filters = IssueFilters {
+            since: at("2024-10-14T00:00:00Z"),
+            before: at("2024-10-28T00:00:00Z"),
+            ..done_filters()
+        };
+        filters.widen_window(&IssueFilters {
+            since: at("2024-10-07T00:00:00Z"),
+            before: at("2024-10-21T00:00:00Z"),
+            ..done_filters()
+        });
+        assert_eq!(filters.since, at("2024-10-07T00:00:00Z"));
+        assert_eq!(filters.before, at("2024-10-28T00:00:00Z"));
+
+        filters.widen_window(&done_filters());
+        assert_eq!((filters.since, filters.before), (None, None));
+    }
+
+    #[test]
+    fn test_date_source_parse() {
+        assert_eq!(DateSource::parse("closed").unwrap(), DateSource::Closed);
+        assert_eq!(
+            DateSource::parse("project:Shipped Date").unwrap(),
+            DateSource::Project("Shipped Date".to_string())
+        );
+        assert_eq!(DateSource::parse("Project: Due").unwrap(), DateSource::Project("Due".to_string()));
+        assert!(DateSource::parse("project:").is_err());
+        assert!(DateSource::parse("Shipped Date").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_filters_on_project_date_field() {
+        let (server, client) = mock_client().await;
+        let shipped = |number, date: Option<&str>| {
+            let mut item = issue_item(number, "Done", None);
+            item["dateValue"] = match date {
+                Some(date) => json!({ "__typename": "ProjectV2ItemFieldDateValue", "date": date }),
+                None => Value::Null,
+            };
+            item
+        };
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "dateField": "Shipped Date", "withDate": true } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                // Every item was closed on 2024-01-15; only the shipped date differs
+                vec![shipped(1, Some("2024-03-10")), shipped(2, Some("2024-02-01")), shipped(3, None)],
+                3,
+                None,
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let filters = IssueFilters {
+            since: Some(crate::time_filter::local_midnight("2024-03-01".parse().unwrap()).unwrap()),
+            date_field: Some(DateSource::Project("Shipped Date".to_string())),
+            ..done_filters()
+        };
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(issues[0].project_date, "2024-03-10".parse().ok());
+        assert_eq!((stats.filtered_by_time, stats.missing_timestamp), (2, 1));
+    }
+
+    #[test]
+    fn test_validate_node_id() {
+        assert!(validate_node_id("PVT_kwDOABCD1234").is_ok());
+        assert!(validate_node_id("PVT_kwHOAbc-_0123456789").is_ok());
+
+        let reason = |id: &str| validate_node_id(id).unwrap_err().reason;
+        assert!(reason("PVT_kwDO").contains("too short"));
+        assert!(reason("PVT_kwDOABCD 1234").contains("characters"));
+        assert!(reason("PVT_kwDOABCD1234\"").contains("characters"));
+        assert!(reason(&format!("PVT_{}", "A".repeat(65))).contains("too long"));
+        assert!(reason("PVTI_kwDOABCD1234").contains("PVT_"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_malformed_node_id() {
+        let (_server, client) = mock_client().await;
+        let err = client.resolve_project_id("PVT_kw").await.unwrap_err();
+        assert!(err.downcast_ref::<InvalidNodeId>().is_some());
+        assert!(err.to_string().contains("owner/number"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_unknown_node_id() {
+        let (server, client) = mock_client().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "node": null },
+                "errors": [{
+                    "type": "NOT_FOUND",
+                    "message": "Could not resolve to a node with the global id of 'PVT_kwDOABCD1234'"
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let err = client
+            .fetch_project_issues("PVT_kwDOABCD1234", &done_filters(), false)
+            .await
+            .unwrap_err();
+        let not_found = err.downcast_ref::<ProjectNotFound>().expect("typed error");
+        assert_eq!(not_found.node_id, "PVT_kwDOABCD1234");
+        assert!(err.to_string().contains("different GitHub host"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_excludes_bots() {
+        let (server, client) = mock_client().await;
+        let mut by_bot = issue_item(1, "Done", None);
+        by_bot["content"]["author"] = json!({ "__typename": "Bot", "login": "dependabot" });
+        let mut assigned_bot = issue_item(2, "Done", None);
+        assigned_bot["content"]["author"] = json!({ "__typename": "User", "login": "alice" });
+        assigned_bot["content"]["assignees"] = json!({ "nodes": [{ "login": "renovate[bot]" }] });
+        let mut human = issue_item(3, "Done", None);
+        human["content"]["author"] = json!({ "__typename": "User", "login": "bob" });
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("author {"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(vec![by_bot, assigned_bot, human], 3, None)))
+            .mount(&server)
+            .await;
+
+        let (issues, _) = client.fetch_project_issues("PVT_test", &done_filters(), false).await.unwrap();
+        assert_eq!(issues[0].author.as_deref(), Some("dependabot[bot]"));
+        assert_eq!(issues[2].author.as_deref(), Some("bob"));
+
+        let filters = IssueFilters {
+            exclude_bots: true,
+            ..done_filters()
+        };
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(stats.filtered_by_bot, 2);
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let (server, client) = mock_client().await;
+        let mut leaky = issue_item(1, "Done", None);
+        leaky["content"]["title"] = json!("Rotate test-token");
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(vec![leaky, issue_item(2, "Done", None)], 2, None)))
+            .mount(&server)
+            .await;
+
+        let dir = std::env::temp_dir().join(format!("doner-fixtures-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let client = client.record_to(&dir).unwrap();
+        let (recorded, _) = client.fetch_project_issues("PVT_test", &done_filters(), false).await.unwrap();
+        let fixture = std::fs::read_to_string(dir.join("0001.json")).unwrap();
+        assert!(fixture.contains("Rotate ***") && !fixture.contains("test-token"), "{}", fixture);
+
+        // Nothing listens on the replaying client's endpoint, so every response comes from disk
+        let offline = GitHubClient::with_graphql_url("", "http://127.0.0.1:9").unwrap().replay_from(&dir);
+        let (replayed, _) = offline.fetch_project_issues("PVT_test", &done_filters(), false).await.unwrap();
+        let numbers = |issues: &[Issue]| issues.iter().map(|i| i.number).collect::<Vec<_>>();
+        assert_eq!(numbers(&replayed), numbers(&recorded));
+        assert_eq!(replayed[0].title, "Rotate ***");
+
+        let err = offline.fetch_project_issues("PVT_test", &done_filters(), false).await.unwrap_err();
+        assert!(err.to_string().contains("0002.json"), "{}", err);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_archived_items() {
+        let (server, client) = mock_client().await;
+
+        let mut archived = issue_item(2, "Done", None);
+        archived["isArchived"] = json!(true);
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(1, "Done", None), archived],
+                2,
+                None,
+            )))
+            .mount(&server)
+            .await;
+
+        for (mode, expected, skipped_active) in [
+            (ArchivedItems::Exclude, vec![1], 0),
+            (ArchivedItems::Include, vec![1, 2], 0),
+            (ArchivedItems::Only, vec![2], 1),
+        ] {
+            let filters = IssueFilters {
+                archived: mode,
+                ..done_filters()
+            };
+            let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+            assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), expected, "{:?}", mode);
+            assert_eq!((stats.archived, stats.skipped_active), (1, skipped_active), "{:?}", mode);
+            assert!(stats.archived_items.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_lists_archived_items() {
+        let (server, client) = mock_client().await;
+
+        let mut archived = issue_item(2, "Todo", None);
+        archived["isArchived"] = json!(true);
+        let mut archived_draft = issue_item(3, "Done", None);
+        archived_draft["isArchived"] = json!(true);
+        archived_draft["content"] = json!({ "__typename": "DraftIssue", "id": "DI_3", "title": "Old idea", "body": null });
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(1, "Done", None), archived, archived_draft],
+                3,
+                None,
+            )))
+            .mount(&server)
+            .await;
+
+        let filters = IssueFilters {
+            list_archived: true,
+            ..done_filters()
+        };
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            stats.archived_items,
+            vec![
+                ArchivedItem {
+                    title: "Issue 2".to_string(),
+                    url: Some("https://github.com/myorg/repo/issues/2".to_string()),
+                    column: Some("Todo".to_string()),
+                },
+                ArchivedItem {
+                    title: "Old idea".to_string(),
+                    url: None,
+                    column: Some("Done".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_field_name_overrides() {
+        let (server, client) = mock_client().await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "variables": { "statusField": "Stage", "iterationField": "Sprint" } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![issue_item(1, "Done", None)],
+                1,
+                None,
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let filters = IssueFilters {
+            status_field: Some("Stage".to_string()),
+            iteration_field: Some("Sprint".to_string()),
+            ..done_filters()
+        };
+        let (issues, _) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_before() {
+        let (server, client) = mock_client().await;
+
+        let closed_on = |number: u64, date: &str| {
+            let mut item = issue_item(number, "Done", None);
+            item["content"]["closedAt"] = json!(format!("{}T12:00:00Z", date));
+            item
+        };
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items_page(
+                vec![closed_on(1, "2024-09-20"), closed_on(2, "2024-10-15"), closed_on(3, "2024-11-01")],
+                3,
+                None,
+            )))
+            .mount(&server)
+            .await;
+
+        let filters = IssueFilters {
+            before: Some("2024-11-01T00:00:00Z".parse().unwrap()),
+            ..done_filters()
+        };
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(stats.filtered_by_time, 1);
+
+        let filters = IssueFilters {
+            since: Some("2024-10-01T00:00:00Z".parse().unwrap()),
+            ..filters
+        };
+        let (issues, stats) = client.fetch_project_issues("PVT_test", &filters, false).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(stats.filtered_by_time, 2);
+    }
+
+    #[test]
+    fn test_fetch_stats_summary() {
+        let stats = FetchStats {
+            wrong_column: 40,
+            filtered_by_time: 3,
+            ..Default::default()
+        };
+        assert_eq!(stats.summary(12, "Done"), "12 issues from 'Done' (filtered 40 by column, 3 by time)");
+        assert_eq!(FetchStats::default().summary(1, "Review"), "1 issue from 'Review'");
+    }
+
+    #[test]
+    fn test_parse_page_size() {
+        assert_eq!(parse_page_size("50").unwrap(), 50);
+        assert_eq!(parse_page_size(" 0 ").unwrap(), 1);
+        assert_eq!(parse_page_size("500").unwrap(), 100);
+        assert!(parse_page_size("-5").is_err());
+        assert!(parse_page_size("lots").is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use anyhow::{anyhow, Result};
+use chrono::{Duration, NaiveDate};
+
+use crate::models::{Iteration, IterationStatus};
+
+/// Default sprint length used when only an iteration's start date is known
+const SPRINT_LENGTH_DAYS: i64 = 14;
+
+/// The date iteration selectors (`--iteration @current`, `--since @current`, `doner iterations`)
+/// are resolved against. Local, like the other time filters, so every selector picks the same
+/// sprint around midnight
+pub fn today() -> NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+/// Classify iterations (sorted by start date) as previous, current, or next relative to `today`.
+/// Previous is the last iteration ending on or before today; next is the first starting after it.
+pub fn classify_iterations(iterations: &[Iteration], today: NaiveDate) -> Vec<Option<IterationStatus>> {
+    let previous = iterations.iter().rposition(|it| it.end_date() <= today);
+    let next = iterations.iter().position(|it| it.start_date > today);
+
+    iterations
+        .iter()
+        .enumerate()
+        .map(|(i, it)| {
+            if it.contains(today) {
+                Some(IterationStatus::Current)
+            } else if Some(i) == previous {
+                Some(IterationStatus::Previous)
+            } else if Some(i) == next {
+                Some(IterationStatus::Next)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Check whether a time filter names an iteration (e.g. `--since @current`) rather than a time
+pub fn is_iteration_selector(input: &str) -> bool {
+    input.trim().starts_with('@')
+}
+
+/// Start date of the iteration named by `@current` or `@previous`, relative to `today`
+pub fn iteration_start(iterations: &[Iteration], selector: &str, today: NaiveDate) -> Result<NaiveDate> {
+    let wanted = match selector.trim().to_lowercase().as_str() {
+        "@current" => IterationStatus::Current,
+        "@previous" => IterationStatus::Previous,
+        _ => return Err(anyhow!("Invalid iteration selector '{}'. Use @current or @previous", selector)),
+    };
+
+    classify_iterations(iterations, today)
+        .into_iter()
+        .zip(iterations)
+        .find(|(status, _)| *status == Some(wanted))
+        .map(|(_, iteration)| iteration.start_date)
+        .ok_or_else(|| anyhow!("The project has no {} iteration", selector.trim()))
+}
+
+/// Check if an item's iteration matches the filter.
+/// Supported filter formats:
+/// - `@all` - matches all iterations (no filtering)
+/// - `@current` - matches the iteration that contains `today`
+/// - `@previous` - matches the iteration before current
+/// - `@current,@previous` - matches either current or previous
+/// - `<iteration name>` - exact match on iteration title
+pub fn matches_iteration_filter(
+    iteration_title: Option<&str>,
+    iteration_start: Option<&str>,
+    filter: &str,
+    today: NaiveDate,
+) -> bool {
+    // @all means no filtering
+    if filter == "@all" {
+        return true;
+    }
+
+    // If filter requires an iteration but item has none, no match
+    if filter.starts_with('@') && iteration_title.is_none() {
+        return false;
+    }
+
+    let start = iteration_start.and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
+
+    // Parse filter parts (e.g., "@current,@previous")
+    for part in filter.split(',').map(|s| s.trim()) {
+        let matched = match part {
+            "@current" => start.is_some_and(|start| is_current_iteration(start, today)),
+            // Without the full iteration list, previous is the sprint right before the current one
+            "@previous" => start.is_some_and(|start| is_recent_past_iteration(start, today)),
+            name => iteration_title == Some(name),
+        };
+        if matched {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Check if an iteration starting on `start` contains `today`.
+/// Assumes 2-week sprints.
+pub fn is_current_iteration(start: NaiveDate, today: NaiveDate) -> bool {
+    start <= today && today < start + Duration::days(SPRINT_LENGTH_DAYS)
+}
+
+/// Check if an iteration starting on `start` is the one right before the current sprint.
+/// Assumes 2-week sprints, so it started 15-28 days ago and ended by yesterday.
+pub fn is_recent_past_iteration(start: NaiveDate, today: NaiveDate) -> bool {
+    start > today - Duration::days(SPRINT_LENGTH_DAYS * 2) && start <= today - Duration::days(SPRINT_LENGTH_DAYS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn iteration(title: &str, start: &str) -> Iteration {
+        Iteration {
+            title: title.to_string(),
+            start_date: date(start),
+            duration: 14,
+        }
+    }
+
+    #[test]
+    fn test_iteration_start() {
+        let iterations = vec![iteration("Sprint 12", "2024-09-30"), iteration("Sprint 13", "2024-10-14")];
+        let today = date("2024-10-20");
+
+        assert_eq!(iteration_start(&iterations, "@current", today).unwrap(), date("2024-10-14"));
+        assert_eq!(iteration_start(&iterations, "@Previous", today).unwrap(), date("2024-09-30"));
+        assert!(iteration_start(&iterations, "@next", today).is_err());
+
+        let err = iteration_start(&iterations, "@current", date("2024-12-01")).unwrap_err();
+        assert!(err.to_string().contains("no @current iteration"));
+    }
+
+    #[test]
+    fn test_current_iteration_bounds() {
+        let start = date("2024-10-14");
+        assert!(is_current_iteration(start, date("2024-10-14")));
+        assert!(is_current_iteration(start, date("2024-10-27")));
+        assert!(!is_current_iteration(start, date("2024-10-28")));
+        assert!(!is_current_iteration(start, date("2024-10-13")));
+    }
+
+    #[test]
+    fn test_previous_iteration_has_no_gap() {
+        let today = date("2024-10-28");
+        // Sprint that ended yesterday is the previous one
+        assert!(is_recent_past_iteration(date("2024-10-14"), today));
+        assert!(!is_current_iteration(date("2024-10-14"), today));
+        assert!(is_recent_past_iteration(date("2024-10-01"), today));
+        // Two sprints back is neither
+        assert!(!is_recent_past_iteration(date("2024-09-30"), today));
+        assert!(!is_recent_past_iteration(date("2024-10-28"), today));
+    }
+
+    #[test]
+    fn test_matches_iteration_filter() {
+        let today = date("2024-10-20");
+        let current = (Some("Sprint 3"), Some("2024-10-14"));
+        let previous = (Some("Sprint 2"), Some("2024-09-30"));
+        let older = (Some("Sprint 1"), Some("2024-09-16"));
+
+        for (title, start) in [current, previous, older] {
+            assert!(matches_iteration_filter(title, start, "@all", today));
+        }
+        assert!(matches_iteration_filter(current.0, current.1, "@current", today));
+        assert!(!matches_iteration_filter(previous.0, previous.1, "@current", today));
+        assert!(matches_iteration_filter(previous.0, previous.1, "@previous", today));
+        assert!(matches_iteration_filter(previous.0, previous.1, "@current, @previous", today));
+        assert!(!matches_iteration_filter(older.0, older.1, "@current,@previous", today));
+        assert!(matches_iteration_filter(older.0, older.1, "Sprint 1", today));
+        assert!(!matches_iteration_filter(older.0, older.1, "Sprint 2", today));
+    }
+
+    #[test]
+    fn test_no_iteration_never_matches() {
+        let today = date("2024-10-20");
+        assert!(!matches_iteration_filter(None, None, "@current", today));
+        assert!(!matches_iteration_filter(None, None, "Sprint 1", today));
+        assert!(matches_iteration_filter(None, None, "@all", today));
+    }
+
+    #[test]
+    fn test_classify_iterations() {
+        let iterations = vec![
+            iteration("Sprint 1", "2024-09-16"),
+            iteration("Sprint 2", "2024-09-30"),
+            iteration("Sprint 3", "2024-10-14"),
+            iteration("Sprint 4", "2024-10-28"),
+        ];
+
+        assert_eq!(
+            classify_iterations(&iterations, date("2024-10-14")),
+            vec![
+                None,
+                Some(IterationStatus::Previous),
+                Some(IterationStatus::Current),
+                Some(IterationStatus::Next),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classify_iterations_in_gap() {
+        // Break between sprints: nothing is current
+        let iterations = vec![iteration("Sprint 1", "2024-09-01"), iteration("Sprint 2", "2024-10-01")];
+
+        assert_eq!(
+            classify_iterations(&iterations, date("2024-09-20")),
+            vec![Some(IterationStatus::Previous), Some(IterationStatus::Next)]
+        );
+    }
+
+    #[test]
+    fn test_selectors_agree_on_today() {
+        // Every selector resolves against the same day, so they pick the same sprint
+        let today = today();
+        let current = today - Duration::days(3);
+        let previous = current - Duration::days(SPRINT_LENGTH_DAYS);
+        let iterations = vec![
+            Iteration {
+                title: "Sprint 1".to_string(),
+                start_date: previous,
+                duration: 14,
+            },
+            Iteration {
+                title: "Sprint 2".to_string(),
+                start_date: current,
+                duration: 14,
+            },
+        ];
+
+        assert_eq!(
+            classify_iterations(&iterations, today),
+            vec![Some(IterationStatus::Previous), Some(IterationStatus::Current)]
+        );
+        assert_eq!(iteration_start(&iterations, "@current", today).unwrap(), current);
+        assert_eq!(iteration_start(&iterations, "@previous", today).unwrap(), previous);
+
+        let start = current.format("%Y-%m-%d").to_string();
+        assert!(matches_iteration_filter(Some("Sprint 2"), Some(&start), "@current", today));
+        let start = previous.format("%Y-%m-%d").to_string();
+        assert!(matches_iteration_filter(Some("Sprint 1"), Some(&start), "@previous", today));
+    }
+}
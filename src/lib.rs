@@ -0,0 +1,87 @@
+//! Fetch and summarize issues from GitHub Projects (v2) boards.
+//!
+//! The `doner` binary is a thin CLI over this crate. The same building blocks can be
+//! used from other tools: fetch issues with [`GitHubClient`], narrow them down with
+//! [`parse_time_filter`], and render them with [`format_list`] or [`format_grouped`].
+//!
+//! ```
+//! use doner::{format_list, Issue, OutputFormat, OutputOptions};
+//!
+//! let issues = vec![Issue {
+//!     number: 42,
+//!     title: "Fix login button alignment".to_string(),
+//!     url: "https://github.com/myorg/repo/issues/42".to_string(),
+//!     repository: "myorg/repo".to_string(),
+//!     ..Default::default()
+//! }];
+//!
+//! let options = OutputOptions {
+//!     format: OutputFormat::Markdown,
+//!     ..OutputOptions::default()
+//! };
+//! let summary = format_list(&issues, &options);
+//! assert!(summary.contains("[myorg/repo#42](https://github.com/myorg/repo/issues/42)"));
+//! ```
+//!
+//! Fetching needs a GitHub token with access to the project:
+//!
+//! ```no_run
+//! # async fn run() -> anyhow::Result<()> {
+//! use doner::github::{GitHubClient, IssueFilters};
+//!
+//! let client = GitHubClient::new("ghp_your_token_here")?;
+//! let project = client.resolve_project_id("myorg/5").await?;
+//! let filters = IssueFilters {
+//!     column: "Done".to_string(),
+//!     since: Some(doner::parse_time_filter("7d")?),
+//!     ..Default::default()
+//! };
+//! let (issues, _stats) = client.fetch_project_issues(&project.node_id, &filters, false).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use clap::ValueEnum;
+
+pub mod app_auth;
+pub mod auth;
+pub mod color;
+pub mod diff;
+pub mod email;
+pub mod git;
+pub mod github;
+pub mod http;
+pub mod iteration_filter;
+pub mod llm;
+pub mod locale;
+pub mod merge;
+pub mod models;
+pub mod output;
+pub mod state;
+pub mod template;
+pub mod time_filter;
+pub mod webhook;
+
+pub use github::GitHubClient;
+pub use models::Issue;
+pub use output::{format_grouped, format_list, OutputOptions};
+pub use time_filter::parse_time_filter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Markdown,
+    /// Keep-a-Changelog style markdown, sectioned by issue labels
+    Changelog,
+    /// GitHub Release body: a "What's Changed" list and the contributing assignees
+    GithubRelease,
+    /// Emacs Org-mode headings with property drawers
+    Org,
+    /// Bare issue URLs, one per line
+    Urls,
+    /// Aligned columns (Repo, #, Title, Closed, Parent) for scanning in a terminal
+    Table,
+    /// Mermaid flowchart linking parent issues to their children
+    Mermaid,
+}
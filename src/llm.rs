@@ -1,7 +1,13 @@
 use anyhow::{anyhow, Context, Result};
-use std::process::Stdio;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeSet;
+use std::process::{Output, Stdio};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 
+use crate::models::Issue;
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub enum LlmProvider {
@@ -10,77 +16,383 @@ pub enum LlmProvider {
     Custom(String), // custom command
 }
 
+impl LlmProvider {
+    /// Build a provider from a name in DONER_LLM_PROVIDERS.
+    /// Unknown names are treated as a CLI executable that receives the prompt as its last argument.
+    fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "gemini" => {
+                if !is_command_available("gemini") {
+                    return Err(anyhow!("gemini-cli is not installed"));
+                }
+                Ok(LlmProvider::Gemini)
+            }
+            "cursor" => {
+                if !is_command_available("agent") {
+                    return Err(anyhow!("cursor CLI is not installed"));
+                }
+                Ok(LlmProvider::Cursor)
+            }
+            "custom" => std::env::var("DONER_LLM_CMD")
+                .map(LlmProvider::Custom)
+                .map_err(|_| anyhow!("DONER_LLM_CMD is not set")),
+            cmd => {
+                if !is_command_available(cmd) {
+                    return Err(anyhow!("'{}' is not installed", cmd));
+                }
+                Ok(LlmProvider::Custom(cmd.to_string()))
+            }
+        }
+    }
+
+    /// Human-readable provider name
+    pub fn name(&self) -> &str {
+        match self {
+            LlmProvider::Gemini => "gemini-cli",
+            LlmProvider::Cursor => "cursor CLI",
+            LlmProvider::Custom(cmd) => cmd,
+        }
+    }
+}
+
+/// Default time limit for a single LLM CLI call
+const DEFAULT_TIMEOUT_SECS: u64 = 120;
+
+/// Read the LLM call timeout from DONER_LLM_TIMEOUT (in seconds)
+fn timeout_from_env() -> Result<Duration> {
+    crate::http::parse_timeout_secs("DONER_LLM_TIMEOUT", DEFAULT_TIMEOUT_SECS)
+}
+
+/// Default --ai-chunk-size: issue text per LLM call, in characters (roughly 15k tokens)
+pub const DEFAULT_CHUNK_CHARS: usize = 60_000;
+
+/// Whether DONER_LLM_STDIN asks for the prompt on the custom command's stdin
+fn prompt_stdin_from_env() -> bool {
+    std::env::var("DONER_LLM_STDIN").is_ok_and(|value| matches!(value.trim(), "1" | "true" | "yes"))
+}
+
+/// A generated summary and the provider that produced it
+#[derive(Debug)]
+pub struct Summary {
+    pub text: String,
+    pub provider: String,
+    /// Number of batches the issues were summarized in (1 without chunking)
+    pub parts: usize,
+}
+
+/// Structured facts about the summarized work, woven into the prompt
+#[derive(Debug, Clone, Default)]
+pub struct SummaryContext {
+    pub project: String,
+    pub total: usize,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub repositories: Vec<String>,
+}
+
+impl SummaryContext {
+    /// Build context from the fetched issues.
+    /// The date range starts at the time filter (if any) or the earliest close date.
+    pub fn from_issues(project: &str, issues: &[Issue], since: Option<DateTime<Utc>>) -> Self {
+        let closed = issues.iter().filter_map(|i| i.closed_at);
+        let repositories: BTreeSet<&str> = issues.iter().map(|i| i.repository.as_str()).collect();
+
+        Self {
+            project: project.to_string(),
+            total: issues.len(),
+            start: since.or_else(|| closed.clone().min()),
+            end: closed.max(),
+            repositories: repositories.into_iter().map(String::from).collect(),
+        }
+    }
+
+    /// Describe the context as a sentence for the prompt
+    pub fn describe(&self) -> String {
+        let mut sentence = format!("These {} tasks from project {} were completed", self.total, self.project);
+
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => sentence.push_str(&format!(
+                " between {} and {}",
+                start.format("%Y-%m-%d"),
+                end.format("%Y-%m-%d")
+            )),
+            (Some(start), None) => sentence.push_str(&format!(" since {}", start.format("%Y-%m-%d"))),
+            (None, Some(end)) => sentence.push_str(&format!(" up to {}", end.format("%Y-%m-%d"))),
+            (None, None) => {}
+        }
+
+        if !self.repositories.is_empty() {
+            sentence.push_str(&format!(" across repos {}", self.repositories.join(", ")));
+        }
+
+        sentence.push('.');
+        sentence
+    }
+}
+
 pub struct LlmClient {
-    provider: LlmProvider,
+    /// Providers to try, in order, until one succeeds
+    providers: Vec<LlmProvider>,
+    context: Option<SummaryContext>,
+    /// Extra instructions appended after the built-in prompt
+    prompt_append: Option<String>,
+    /// Write the prompt to a custom command's stdin instead of passing it as an argument
+    prompt_stdin: bool,
+    timeout: Duration,
+    /// Summarize longer issue lists in batches of at most this many characters
+    chunk_size: Option<usize>,
 }
 
 impl LlmClient {
     /// Create a new LLM client, auto-detecting available CLI tools
     pub fn from_env() -> Result<Self> {
+        // Check for an explicit ordered provider list
+        if let Ok(list) = std::env::var("DONER_LLM_PROVIDERS") {
+            return Self::from_provider_list(&list);
+        }
+
         // Check for explicit provider override
         if let Ok(cmd) = std::env::var("DONER_LLM_CMD") {
             return Ok(Self {
-                provider: LlmProvider::Custom(cmd),
+                providers: vec![LlmProvider::Custom(cmd)],
+                context: None,
+                prompt_append: None,
+                prompt_stdin: prompt_stdin_from_env(),
+                timeout: timeout_from_env()?,
+                chunk_size: Some(DEFAULT_CHUNK_CHARS),
             });
         }
 
-        // Auto-detect available CLI tools
+        // Auto-detect available CLI tools, falling back between them in order
+        let mut providers = Vec::new();
         if is_command_available("gemini") {
-            return Ok(Self {
-                provider: LlmProvider::Gemini,
-            });
+            providers.push(LlmProvider::Gemini);
         }
-
         if is_command_available("agent") {
-            return Ok(Self {
-                provider: LlmProvider::Cursor,
-            });
+            providers.push(LlmProvider::Cursor);
         }
 
-        Err(anyhow!(
-            "No LLM CLI tool found. Install one of:\n  \
-             - gemini-cli (https://github.com/google-gemini/gemini-cli)\n  \
-             - cursor CLI\n  \
-             Or set DONER_LLM_CMD to a custom command"
-        ))
+        if providers.is_empty() {
+            return Err(anyhow!(
+                "No LLM CLI tool found. Install one of:\n  \
+                 - gemini-cli (https://github.com/google-gemini/gemini-cli)\n  \
+                 - cursor CLI\n  \
+                 Or set DONER_LLM_CMD to a custom command"
+            ));
+        }
+
+        Ok(Self {
+            providers,
+            context: None,
+            prompt_append: None,
+            prompt_stdin: prompt_stdin_from_env(),
+            timeout: timeout_from_env()?,
+            chunk_size: Some(DEFAULT_CHUNK_CHARS),
+        })
     }
 
-    /// Generate a rich summary from pre-formatted issue list
-    pub async fn summarize(&self, formatted_issues: &str) -> Result<String> {
+    /// Build a client from a comma-separated provider list (e.g. "gemini,cursor,ollama").
+    /// Providers that can't be constructed are skipped with a warning.
+    fn from_provider_list(list: &str) -> Result<Self> {
+        let mut providers = Vec::new();
+        let mut skipped = Vec::new();
+
+        for name in list.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+            match LlmProvider::from_name(name) {
+                Ok(provider) => providers.push(provider),
+                Err(e) => {
+                    eprintln!("Warning: skipping LLM provider '{}': {}", name, e);
+                    skipped.push(format!("{}: {}", name, e));
+                }
+            }
+        }
+
+        if providers.is_empty() {
+            return Err(anyhow!(
+                "No usable LLM provider in DONER_LLM_PROVIDERS:\n  {}",
+                skipped.join("\n  ")
+            ));
+        }
+
+        Ok(Self {
+            providers,
+            context: None,
+            prompt_append: None,
+            prompt_stdin: prompt_stdin_from_env(),
+            timeout: timeout_from_env()?,
+            chunk_size: Some(DEFAULT_CHUNK_CHARS),
+        })
+    }
+
+    /// Attach structured context (date range, project, repositories) to the prompt
+    pub fn with_context(mut self, context: SummaryContext) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Append one-off instructions (e.g. "be concise, max 5 bullets") to the prompt
+    pub fn with_prompt_append(mut self, text: impl Into<String>) -> Self {
+        self.prompt_append = Some(text.into());
+        self
+    }
+
+    /// Split issue lists longer than `chars` into batches that are summarized separately and
+    /// then merged; 0 always sends the whole list in one call
+    pub fn with_chunk_size(mut self, chars: usize) -> Self {
+        self.chunk_size = (chars > 0).then_some(chars);
+        self
+    }
+
+    /// Generate a rich summary from pre-formatted issue list,
+    /// trying each provider in order until one succeeds.
+    /// Lists over the chunk size are summarized batch by batch, then the batch summaries are merged
+    pub async fn summarize(&self, formatted_issues: &str) -> Result<Summary> {
+        let chunks = match self.chunk_size {
+            Some(size) if formatted_issues.len() > size => split_into_chunks(formatted_issues, size),
+            _ => Vec::new(),
+        };
+        if chunks.len() < 2 {
+            let (text, provider) = self.complete(&self.build_prompt(formatted_issues)).await?;
+            return Ok(Summary { text, provider, parts: 1 });
+        }
+
+        let mut partials = Vec::with_capacity(chunks.len());
+        for (i, chunk) in chunks.iter().enumerate() {
+            let (text, _) = self
+                .complete(&self.build_batch_prompt(chunk, i + 1, chunks.len()))
+                .await
+                .with_context(|| format!("Failed to summarize part {} of {}", i + 1, chunks.len()))?;
+            partials.push(text);
+        }
+        let (text, provider) = self.complete(&self.build_merge_prompt(&partials)).await?;
+        Ok(Summary {
+            text,
+            provider,
+            parts: chunks.len(),
+        })
+    }
+
+    /// Send a prompt to each provider in order until one succeeds, returning the text and provider name
+    async fn complete(&self, prompt: &str) -> Result<(String, String)> {
+        let mut failures = Vec::new();
+
+        for provider in &self.providers {
+            match self.call_provider(provider, prompt).await {
+                Ok(text) => return Ok((text, provider.name().to_string())),
+                Err(e) => {
+                    if self.providers.len() > 1 {
+                        eprintln!("\nWarning: {} failed, trying next provider: {}", provider.name(), e);
+                    }
+                    failures.push(e);
+                }
+            }
+        }
+
+        match failures.len() {
+            1 => Err(failures.remove(0)),
+            _ => Err(anyhow!(
+                "All LLM providers failed:\n  {}",
+                failures.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("\n  ")
+            )),
+        }
+    }
+
+    /// Build the prompt: the built-in instructions, the summary context, the issues,
+    /// then any appended instructions
+    fn build_prompt(&self, formatted_issues: &str) -> String {
+        let context = self
+            .context
+            .as_ref()
+            .map(|c| format!("{}\n\n", c.describe()))
+            .unwrap_or_default();
+
         let prompt = format!(
-            "You are a technical writer summarizing completed software development tasks. 
-Your goal is to create clear, concise summaries that highlight:
-- What was accomplished
-- The impact or value of the work
-- Any patterns or themes across multiple tasks
+            "{}\n\nSummarize the following completed tasks:\n\n{}{}",
+            INSTRUCTIONS, context, formatted_issues
+        );
+        self.with_appended(prompt)
+    }
 
-Write in a professional but accessible tone. Group related work together when it makes sense.
-Use bullet points for clarity. Keep the summary focused and avoid unnecessary jargon. 
-Include links to the issues in the summary if available. 
-Use heading 4 for each theme and avoid using heading 1 to 3. Do not use bold formatting on headings.
+    /// Prompt for one batch of a chunked list. Context and appended instructions are left
+    /// for the merge step, which writes the final report
+    fn build_batch_prompt(&self, chunk: &str, part: usize, parts: usize) -> String {
+        format!(
+            "{}\n\nThe task list is too long for one pass, so it is summarized in {} parts that will be \
+             merged afterwards. Summarize only the tasks of part {}, keeping the issue links:\n\n{}",
+            INSTRUCTIONS, parts, part, chunk
+        )
+    }
 
-Summarize the following completed tasks:
+    /// Prompt that merges the batch summaries into one report
+    fn build_merge_prompt(&self, partials: &[String]) -> String {
+        let context = self
+            .context
+            .as_ref()
+            .map(|c| format!("{}\n\n", c.describe()))
+            .unwrap_or_default();
 
-{}",
-            formatted_issues
+        let prompt = format!(
+            "{}\n\nThe completed tasks were summarized in {} parts. Merge these partial summaries into a \
+             single summary, combining themes that appear in more than one part:\n\n{}{}",
+            INSTRUCTIONS,
+            partials.len(),
+            context,
+            partials.join("\n\n---\n\n")
         );
+        self.with_appended(prompt)
+    }
 
-        match &self.provider {
-            LlmProvider::Gemini => self.call_gemini_cli(&prompt).await,
-            LlmProvider::Cursor => self.call_cursor_cli(&prompt).await,
-            LlmProvider::Custom(cmd) => self.call_custom_cli(cmd, &prompt).await,
+    fn with_appended(&self, prompt: String) -> String {
+        match self.prompt_append.as_deref().map(str::trim) {
+            Some(extra) if !extra.is_empty() => format!("{}\n\n{}", prompt, extra),
+            _ => prompt,
         }
     }
 
-    async fn call_gemini_cli(&self, prompt: &str) -> Result<String> {
-        let output = Command::new("gemini")
-            .arg(&prompt)
-            .stdin(Stdio::null())
+    async fn call_provider(&self, provider: &LlmProvider, prompt: &str) -> Result<String> {
+        match provider {
+            LlmProvider::Gemini => self.call_gemini_cli(prompt).await,
+            LlmProvider::Cursor => self.call_cursor_cli(prompt).await,
+            LlmProvider::Custom(cmd) => self.call_custom_cli(cmd, prompt).await,
+        }
+    }
+
+    /// Run a CLI to completion, killing it if it exceeds the configured timeout.
+    /// `input` is written to the child's stdin, which is then closed.
+    async fn run_with_timeout(&self, command: &mut Command, name: &str, input: Option<&str>) -> Result<Output> {
+        let mut child = command
+            .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
-            .await
-            .context("Failed to execute gemini-cli")?;
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to execute {}", name))?;
+
+        if let Some(input) = input
+            && let Some(mut stdin) = child.stdin.take()
+        {
+            let input = input.as_bytes().to_vec();
+            // Written concurrently so a child filling its stdout pipe can't deadlock; dropping closes stdin
+            tokio::spawn(async move {
+                let _ = stdin.write_all(&input).await;
+            });
+        }
+
+        match tokio::time::timeout(self.timeout, child.wait_with_output()).await {
+            Ok(output) => output.with_context(|| format!("Failed to read {} output", name)),
+            // Dropping the wait future kills the child (kill_on_drop)
+            Err(_) => Err(anyhow!(
+                "{} timed out after {}s (set DONER_LLM_TIMEOUT to adjust)",
+                name,
+                self.timeout.as_secs()
+            )),
+        }
+    }
+
+    async fn call_gemini_cli(&self, prompt: &str) -> Result<String> {
+        let output = self
+            .run_with_timeout(Command::new("gemini").arg(prompt), "gemini-cli", None)
+            .await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -92,19 +404,9 @@ Summarize the following completed tasks:
     }
 
     async fn call_cursor_cli(&self, prompt: &str) -> Result<String> {
-        // Cursor CLI uses stdin for prompts
-        let child = Command::new("agent")
-            .arg(&prompt)
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .context("Failed to execute cursor CLI")?;
-
-        let output = child
-            .wait_with_output()
-            .await
-            .context("Failed to read cursor CLI output")?;
+        let output = self
+            .run_with_timeout(Command::new("agent").arg(prompt), "cursor CLI", None)
+            .await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -116,23 +418,15 @@ Summarize the following completed tasks:
     }
 
     async fn call_custom_cli(&self, cmd: &str, prompt: &str) -> Result<String> {
-        // Parse the command - first word is the executable, rest are base args
-        let parts: Vec<&str> = cmd.split_whitespace().collect();
-        if parts.is_empty() {
-            return Err(anyhow!("DONER_LLM_CMD is empty"));
-        }
+        let (executable, args) = build_custom_command(cmd, prompt, self.prompt_stdin)?;
 
-        let (executable, base_args) = (parts[0], &parts[1..]);
-
-        let output = Command::new(executable)
-            .args(base_args)
-            .arg(&prompt)
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await
-            .context(format!("Failed to execute custom command: {}", cmd))?;
+        let output = self
+            .run_with_timeout(
+                Command::new(&executable).args(&args),
+                &format!("custom command '{}'", cmd),
+                self.prompt_stdin.then_some(prompt),
+            )
+            .await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -144,6 +438,71 @@ Summarize the following completed tasks:
     }
 }
 
+/// Built-in instructions that open every prompt
+const INSTRUCTIONS: &str = "You are a technical writer summarizing completed software development tasks. 
+Your goal is to create clear, concise summaries that highlight:
+- What was accomplished
+- The impact or value of the work
+- Any patterns or themes across multiple tasks
+
+Write in a professional but accessible tone. Group related work together when it makes sense.
+Use bullet points for clarity. Keep the summary focused and avoid unnecessary jargon. 
+Include links to the issues in the summary if available. 
+Use heading 4 for each theme and avoid using heading 1 to 3. Do not use bold formatting on headings.";
+
+/// Split formatted issues into chunks of at most `max_chars`, never splitting an issue: an issue
+/// starts at an unindented line and takes the indented lines below it (URLs, details). An issue
+/// longer than `max_chars` gets a chunk of its own
+fn split_into_chunks(text: &str, max_chars: usize) -> Vec<String> {
+    let mut entries: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match entries.last_mut() {
+            Some(entry) if line.is_empty() || line.starts_with(char::is_whitespace) => {
+                entry.push('\n');
+                entry.push_str(line);
+            }
+            _ => entries.push(line.to_string()),
+        }
+    }
+
+    let mut chunks: Vec<String> = Vec::new();
+    for entry in entries {
+        match chunks.last_mut() {
+            Some(chunk) if chunk.len() + 1 + entry.len() <= max_chars => {
+                chunk.push('\n');
+                chunk.push_str(&entry);
+            }
+            _ => chunks.push(entry),
+        }
+    }
+    chunks.into_iter().map(|chunk| chunk.trim_end().to_string()).filter(|c| !c.is_empty()).collect()
+}
+
+/// Placeholder in DONER_LLM_CMD replaced by the prompt
+const PROMPT_PLACEHOLDER: &str = "{prompt}";
+
+/// Split a custom command with shell-word rules and insert the prompt.
+/// The prompt replaces every `{prompt}` placeholder, or is appended as the last argument
+/// when the command has none and the prompt isn't sent on stdin.
+fn build_custom_command(cmd: &str, prompt: &str, prompt_stdin: bool) -> Result<(String, Vec<String>)> {
+    let mut parts = shlex::split(cmd)
+        .ok_or_else(|| anyhow!("DONER_LLM_CMD has unbalanced quotes: {}", cmd))?
+        .into_iter();
+
+    let executable = parts.next().ok_or_else(|| anyhow!("DONER_LLM_CMD is empty"))?;
+    let mut args: Vec<String> = parts.collect();
+
+    if args.iter().any(|a| a.contains(PROMPT_PLACEHOLDER)) {
+        for arg in args.iter_mut() {
+            *arg = arg.replace(PROMPT_PLACEHOLDER, prompt);
+        }
+    } else if !prompt_stdin {
+        args.push(prompt.to_string());
+    }
+
+    Ok((executable, args))
+}
+
 /// Check if a command is available in PATH
 fn is_command_available(cmd: &str) -> bool {
     std::process::Command::new("which")
@@ -155,3 +514,182 @@ fn is_command_available(cmd: &str) -> bool {
         .unwrap_or(false)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn issue(repo: &str, day: u32) -> Issue {
+        Issue {
+            repository: repo.to_string(),
+            closed_at: Some(Utc.with_ymd_and_hms(2024, 10, day, 12, 0, 0).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_context_from_issues() {
+        let issues = vec![issue("myorg/web", 14), issue("myorg/api", 10), issue("myorg/api", 12)];
+        let context = SummaryContext::from_issues("myorg/5", &issues, None);
+
+        assert_eq!(
+            context.describe(),
+            "These 3 tasks from project myorg/5 were completed between 2024-10-10 and 2024-10-14 across repos myorg/api, myorg/web."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_next_provider() {
+        let client = LlmClient {
+            providers: vec![
+                LlmProvider::Custom("false".to_string()),
+                LlmProvider::Custom("echo".to_string()),
+            ],
+            context: None,
+            prompt_append: None,
+            prompt_stdin: false,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            chunk_size: None,
+        };
+
+        let summary = client.summarize("tasks").await.unwrap();
+        assert_eq!(summary.provider, "echo");
+        assert!(summary.text.ends_with("tasks"));
+    }
+
+    #[test]
+    fn test_custom_command_appends_prompt() {
+        let (exe, args) = build_custom_command("llm -m gpt-4", "hi", false).unwrap();
+        assert_eq!(exe, "llm");
+        assert_eq!(args, vec!["-m", "gpt-4", "hi"]);
+    }
+
+    #[test]
+    fn test_custom_command_quoted_args() {
+        let (exe, args) = build_custom_command(r#"sgpt --role "tech writer" '/tmp/my prompts/a.txt'"#, "hi", false).unwrap();
+        assert_eq!(exe, "sgpt");
+        assert_eq!(args, vec!["--role", "tech writer", "/tmp/my prompts/a.txt", "hi"]);
+    }
+
+    #[test]
+    fn test_custom_command_placeholder() {
+        let (_, args) = build_custom_command("tool --input={prompt} --quiet", "hi there", false).unwrap();
+        assert_eq!(args, vec!["--input=hi there", "--quiet"]);
+    }
+
+    #[test]
+    fn test_custom_command_invalid() {
+        assert!(build_custom_command("", "hi", false).is_err());
+        assert!(build_custom_command("tool 'unterminated", "hi", false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_prompt_append() {
+        let client = LlmClient {
+            providers: vec![LlmProvider::Custom("echo".to_string())],
+            context: None,
+            prompt_append: None,
+            prompt_stdin: false,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            chunk_size: None,
+        }
+        .with_prompt_append("  Be concise, max 5 bullets.\n");
+
+        let prompt = client.build_prompt("- #1 Fix login");
+        assert!(prompt.ends_with("- #1 Fix login\n\nBe concise, max 5 bullets."));
+
+        let summary = client.summarize("tasks").await.unwrap();
+        assert!(summary.text.trim_end().ends_with("Be concise, max 5 bullets."));
+    }
+
+    #[test]
+    fn test_custom_command_prompt_on_stdin() {
+        let (exe, args) = build_custom_command("llm -m gpt-4", "hi", true).unwrap();
+        assert_eq!(exe, "llm");
+        assert_eq!(args, vec!["-m", "gpt-4"]);
+    }
+
+    #[tokio::test]
+    async fn test_prompt_via_stdin() {
+        let client = LlmClient {
+            providers: vec![LlmProvider::Custom("cat".to_string())],
+            context: None,
+            prompt_append: None,
+            prompt_stdin: true,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            chunk_size: None,
+        };
+
+        // Large enough to fill a pipe buffer, which would deadlock a sequential write
+        let tasks = "- #1 Fix login\n".repeat(20_000);
+        let summary = client.summarize(&tasks).await.unwrap();
+        assert!(summary.text.starts_with("You are a technical writer"));
+        assert!(summary.text.ends_with("- #1 Fix login"));
+    }
+
+    #[tokio::test]
+    async fn test_provider_timeout() {
+        let client = LlmClient {
+            providers: vec![LlmProvider::Custom("sh -c 'sleep 5'".to_string())],
+            context: None,
+            prompt_append: None,
+            prompt_stdin: false,
+            timeout: Duration::from_millis(100),
+            chunk_size: None,
+        };
+
+        let err = client.summarize("tasks").await.unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+    }
+
+    #[test]
+    fn test_split_into_chunks() {
+        let text = "• [myorg/api#1] Fix login\n  https://github.com/myorg/api/issues/1\n\
+                    • [myorg/api#2] Add cache\n  https://github.com/myorg/api/issues/2\n\
+                    • [myorg/web#3] Dark mode\n  https://github.com/myorg/web/issues/3";
+        let chunks = split_into_chunks(text, 150);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0].ends_with("myorg/api/issues/2"), "{}", chunks[0]);
+        assert_eq!(chunks[1], "• [myorg/web#3] Dark mode\n  https://github.com/myorg/web/issues/3");
+
+        // An issue longer than the limit is kept whole
+        assert_eq!(split_into_chunks(text, 10).len(), 3);
+        assert_eq!(split_into_chunks(text, 10_000).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_summarize_in_chunks() {
+        // `wc -c` turns each prompt into its size, so the merge prompt shows the batch outputs
+        let client = LlmClient {
+            providers: vec![LlmProvider::Custom("sh -c 'printf \"%s\" \"$1\" | wc -c' sh".to_string())],
+            context: None,
+            prompt_append: None,
+            prompt_stdin: false,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            chunk_size: Some(40),
+        }
+        .with_prompt_append("Be concise.");
+
+        let tasks = "- #1 Fix login button\n- #2 Add dark mode\n- #3 Cache results";
+        let summary = client.summarize(tasks).await.unwrap();
+        assert_eq!(summary.parts, 2);
+
+        let merge = client.build_merge_prompt(&["part one".to_string(), "part two".to_string()]);
+        assert!(merge.contains("summarized in 2 parts"));
+        assert!(merge.ends_with("part one\n\n---\n\npart two\n\nBe concise."));
+        let batch = client.build_batch_prompt("- #3 Cache results", 2, 2);
+        assert!(batch.ends_with("part 2, keeping the issue links:\n\n- #3 Cache results"));
+        assert!(!batch.contains("Be concise."));
+
+        let unchunked = client.with_chunk_size(0).summarize(tasks).await.unwrap();
+        assert_eq!(unchunked.parts, 1);
+    }
+
+    #[test]
+    fn test_context_prefers_time_filter_start() {
+        let since = Utc.with_ymd_and_hms(2024, 10, 7, 0, 0, 0).unwrap();
+        let context = SummaryContext::from_issues("myorg/5", &[issue("myorg/api", 10)], Some(since));
+
+        assert_eq!(context.start, Some(since));
+    }
+}
@@ -0,0 +1,82 @@
+use anyhow::{anyhow, Result};
+
+/// Date and number conventions of a locale, for display only
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    /// BCP 47 tag, e.g. `de-DE`
+    pub tag: &'static str,
+    /// strftime pattern for close dates
+    pub date_format: &'static str,
+    /// Separator between groups of three digits in counts
+    pub thousands_separator: char,
+}
+
+/// Supported locales; a bare language (`de`) picks the first entry for it
+const LOCALES: &[Locale] = &[
+    Locale { tag: "en-US", date_format: "%m/%d/%Y %-I:%M %p", thousands_separator: ',' },
+    Locale { tag: "en-GB", date_format: "%d/%m/%Y %H:%M", thousands_separator: ',' },
+    Locale { tag: "de-DE", date_format: "%d.%m.%Y %H:%M", thousands_separator: '.' },
+    Locale { tag: "fr-FR", date_format: "%d/%m/%Y %H:%M", thousands_separator: '\u{202f}' },
+    Locale { tag: "es-ES", date_format: "%d/%m/%Y %H:%M", thousands_separator: '.' },
+    Locale { tag: "it-IT", date_format: "%d/%m/%Y %H:%M", thousands_separator: '.' },
+    Locale { tag: "nl-NL", date_format: "%d-%m-%Y %H:%M", thousands_separator: '.' },
+    Locale { tag: "pt-BR", date_format: "%d/%m/%Y %H:%M", thousands_separator: '.' },
+    Locale { tag: "sv-SE", date_format: "%Y-%m-%d %H:%M", thousands_separator: '\u{a0}' },
+    Locale { tag: "ja-JP", date_format: "%Y/%m/%d %H:%M", thousands_separator: ',' },
+];
+
+/// Look up a locale by tag, ignoring case and accepting `_` for `-` (so `de_DE` works)
+pub fn parse_locale(tag: &str) -> Result<Locale> {
+    let normalized = tag.trim().replace('_', "-");
+    LOCALES
+        .iter()
+        .find(|locale| locale.tag.eq_ignore_ascii_case(&normalized))
+        .or_else(|| {
+            LOCALES.iter().find(|locale| {
+                locale
+                    .tag
+                    .split_once('-')
+                    .is_some_and(|(language, _)| language.eq_ignore_ascii_case(&normalized))
+            })
+        })
+        .copied()
+        .ok_or_else(|| {
+            let tags: Vec<&str> = LOCALES.iter().map(|locale| locale.tag).collect();
+            anyhow!("Unsupported locale '{}'. Supported locales: {}", tag, tags.join(", "))
+        })
+}
+
+/// Write `n` with its digits grouped in threes, e.g. `12,345`
+pub fn group_digits(n: usize, separator: char) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!(parse_locale("de-DE").unwrap().date_format, "%d.%m.%Y %H:%M");
+        assert_eq!(parse_locale("en_us").unwrap().tag, "en-US");
+        assert_eq!(parse_locale("fr").unwrap().tag, "fr-FR");
+        let err = parse_locale("xx-XX").unwrap_err().to_string();
+        assert!(err.contains("Supported locales: en-US"), "{}", err);
+    }
+
+    #[test]
+    fn test_group_digits() {
+        assert_eq!(group_digits(7, ','), "7");
+        assert_eq!(group_digits(999, ','), "999");
+        assert_eq!(group_digits(1000, ','), "1,000");
+        assert_eq!(group_digits(1234567, '.'), "1.234.567");
+    }
+}
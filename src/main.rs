@@ -1,19 +1,13 @@
-mod auth;
-mod github;
-mod llm;
-mod models;
-mod output;
-mod time_filter;
+use anyhow::{anyhow, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use serde::Serialize;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
-use clap::{Parser, Subcommand, ValueEnum};
-
-#[derive(Debug, Clone, Copy, ValueEnum, Default)]
-pub enum OutputFormat {
-    #[default]
-    Text,
-    Markdown,
-}
+use doner::color::ColorChoice;
+use doner::models::IterationStatus;
+use doner::output::{self, GroupBy, OutputOptions, SortKey, StandalonePlacement};
+use doner::{app_auth, auth, diff, email, git, github, http, iteration_filter, llm, locale, merge, state, template, time_filter, webhook, OutputFormat};
 
 #[derive(Parser, Debug)]
 #[command(name = "doner")]
@@ -22,6 +16,31 @@ pub enum OutputFormat {
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Proxy URL for GitHub requests (overrides HTTPS_PROXY/HTTP_PROXY; may include user:pass@)
+    #[arg(long = "proxy", global = true)]
+    proxy: Option<String>,
+
+    /// GitHub host or API URL to use, e.g. github.example.com for GitHub Enterprise Server
+    /// (overrides DONER_API_URL). Tokens are stored per host
+    #[arg(long = "api-url", global = true, value_name = "HOST")]
+    api_url: Option<String>,
+
+    /// Load environment variables from this file instead of ./.env
+    #[arg(long = "env-file", global = true)]
+    env_file: Option<PathBuf>,
+
+    /// GitHub App ID to authenticate as (overrides DONER_APP_ID)
+    #[arg(long = "app-id", global = true, requires_all = ["app_key", "app_installation_id"])]
+    app_id: Option<String>,
+
+    /// Path to the GitHub App private key (overrides DONER_APP_PRIVATE_KEY)
+    #[arg(long = "app-key", global = true, requires_all = ["app_id", "app_installation_id"])]
+    app_key: Option<PathBuf>,
+
+    /// GitHub App installation ID (overrides DONER_APP_INSTALLATION_ID)
+    #[arg(long = "app-installation-id", global = true, requires_all = ["app_id", "app_key"])]
+    app_installation_id: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -34,40 +53,573 @@ enum Commands {
 
     /// Fetch and summarize issues from a project board column
     #[command(name = "summarize", alias = "sum")]
-    Summarize {
-        /// GitHub Project identifier (owner/number or GraphQL node ID)
-        project_id: String,
+    Summarize(Box<SummarizeArgs>),
 
-        /// Column name to fetch issues from
-        #[arg(short = 'c', long = "col", default_value = "Done")]
-        column: String,
+    /// Weekly AI digest (shorthand for `summarize --ai --format markdown --wrap --since this-week`)
+    Digest(DigestArgs),
 
-        /// Filter issues by time (e.g., 7d, 24h, yesterday, this-week)
-        #[arg(short = 's', long = "since")]
-        since: Option<String>,
+    /// Open the issues matching the summarize filters in the browser
+    Open(Box<OpenArgs>),
 
-        /// Filter by iteration (e.g., @current, @previous, @current,@previous, or iteration name)
-        #[arg(short = 'i', long = "iteration")]
-        iteration: Option<String>,
+    /// Compare the issues of two iterations: counts, repositories, and added/removed issues
+    Diff(Box<DiffArgs>),
 
-        /// Output format
-        #[arg(short = 'f', long = "format", value_enum, default_value = "text")]
-        format: OutputFormat,
+    /// List the status columns of a project board
+    Columns {
+        /// GitHub Project identifier (owner/number or GraphQL node ID). Defaults to the last-used project
+        project_id: Option<String>,
+    },
+
+    /// List the iterations of a project board
+    Iterations {
+        /// GitHub Project identifier (owner/number or GraphQL node ID). Defaults to the last-used project
+        project_id: Option<String>,
+    },
 
-        /// Group issues by parent issue
-        #[arg(short = 'w', long = "wrap")]
-        wrap: bool,
+    /// List the built-in output themes
+    Themes,
 
-        /// Use AI to generate a rich summary (requires OPENAI_API_KEY or ANTHROPIC_API_KEY)
-        #[arg(long = "ai")]
-        ai: bool,
+    /// Print the JSON Schema of a serialized issue
+    #[command(name = "json-schema", hide = true)]
+    JsonSchema,
 
-        /// Show debug information about fetched items
-        #[arg(long = "debug")]
-        debug: bool,
+    /// Print version information
+    Version {
+        /// Include the git commit, build date, rustc version and target
+        #[arg(short = 'v', long = "verbose")]
+        verbose: bool,
     },
 }
 
+/// Flags selecting which issues to fetch, shared by summarize, open, and diff
+#[derive(Args, Debug, Default)]
+struct FilterArgs {
+    /// Column name to fetch issues from
+    #[arg(short = 'c', long = "col", default_value = "Done")]
+    column: String,
+
+    /// Filter issues by time (e.g., 7d, 24h, yesterday, this-week, or @current for the iteration's start)
+    #[arg(short = 's', long = "since")]
+    since: Option<String>,
+
+    /// Only include issues closed before this time (same formats as --since)
+    #[arg(long = "before")]
+    before: Option<String>,
+
+    /// Only include issues updated within this window (same formats as --since; combined with it)
+    #[arg(long = "active-since")]
+    active_since: Option<String>,
+
+    /// Filter by iteration (e.g., @current, @previous, @current,@previous, or iteration name)
+    #[arg(short = 'i', long = "iteration")]
+    iteration: Option<String>,
+
+    /// How --iteration and --since combine: issues must match both (and) or either (or)
+    #[arg(long = "filter-mode", value_enum, default_value = "and")]
+    filter_mode: github::FilterMode,
+
+    /// Only include issues closed by this GitHub user
+    #[arg(long = "closed-by")]
+    closed_by: Option<String>,
+
+    /// Only include issues whose milestone is due before this date (e.g., 2024-12-01)
+    #[arg(long = "milestone-due-before", value_name = "DATE")]
+    milestone_due_before: Option<String>,
+
+    /// Only include issues closed as completed, dropping not-planned and duplicate closes
+    #[arg(long = "completed-only")]
+    completed_only: bool,
+
+    /// Exclude issues opened by or assigned to bot accounts (e.g., dependabot, renovate)
+    #[arg(long = "no-bots")]
+    no_bots: bool,
+
+    /// Only include issues whose title matches this regex
+    #[arg(long = "title-match", value_name = "REGEX")]
+    title_match: Option<String>,
+
+    /// Exclude issues whose title matches this regex
+    #[arg(long = "title-exclude", value_name = "REGEX")]
+    title_exclude: Option<String>,
+
+    /// Keep open issues in the column, matching --since against their creation date
+    #[arg(long = "include-open")]
+    include_open: bool,
+
+    /// Name of the board's status field (overrides DONER_STATUS_FIELD)
+    #[arg(long = "status-field", value_name = "NAME")]
+    status_field: Option<String>,
+
+    /// Name of the board's iteration field (overrides DONER_ITERATION_FIELD)
+    #[arg(long = "iteration-field", value_name = "NAME")]
+    iteration_field: Option<String>,
+
+    /// Date --since/--before compare against: `closed` or `project:<Name>` for a project date field
+    /// (overrides DONER_DATE_FIELD)
+    #[arg(long = "date-field", value_name = "SOURCE")]
+    date_field: Option<String>,
+
+    /// Also include archived project items
+    #[arg(long = "include-archived")]
+    include_archived: bool,
+
+    /// Only include archived project items
+    #[arg(long = "only-archived", conflicts_with = "include_archived")]
+    only_archived: bool,
+}
+
+impl FilterArgs {
+    /// Build the fetch filters from the parsed flags
+    fn issue_filters(&self) -> Result<github::IssueFilters> {
+        // Iteration selectors such as @current are resolved once the project is known
+        let time_filter = |value: &Option<String>| {
+            value
+                .as_deref()
+                .filter(|v| !iteration_filter::is_iteration_selector(v))
+                .map(time_filter::parse_time_filter)
+                .transpose()
+        };
+        let title_regex = |value: &Option<String>, flag| {
+            value
+                .as_deref()
+                .map(|p| github::parse_title_regex(p, flag))
+                .transpose()
+        };
+
+        Ok(github::IssueFilters {
+            column: self.column.clone(),
+            since: time_filter(&self.since)?,
+            before: time_filter(&self.before)?,
+            active_since: time_filter(&self.active_since)?,
+            iteration: self.iteration.clone(),
+            closed_by: self.closed_by.clone(),
+            milestone_due_before: self
+                .milestone_due_before
+                .as_deref()
+                .map(time_filter::parse_time_filter)
+                .transpose()?,
+            completed_only: self.completed_only,
+            exclude_bots: self.no_bots,
+            include_open: self.include_open,
+            status_field: self.status_field.clone(),
+            iteration_field: self.iteration_field.clone(),
+            date_field: self.date_field.as_deref().map(github::DateSource::parse).transpose()?,
+            archived: if self.only_archived {
+                github::ArchivedItems::Only
+            } else if self.include_archived {
+                github::ArchivedItems::Include
+            } else {
+                github::ArchivedItems::Exclude
+            },
+            mode: self.filter_mode,
+            title_match: title_regex(&self.title_match, "--title-match")?,
+            title_exclude: title_regex(&self.title_exclude, "--title-exclude")?,
+            ..Default::default()
+        })
+    }
+
+    /// Resolve `--since @current`-style values (also for --before and --active-since)
+    /// to the start of that iteration
+    async fn resolve_iteration_bounds(
+        &self,
+        client: &github::GitHubClient,
+        project_node_id: &str,
+        filters: &mut github::IssueFilters,
+    ) -> Result<()> {
+        let iteration_field = filters.iteration_field();
+        let mut iterations = None;
+        for (value, bound) in [
+            (&self.since, &mut filters.since),
+            (&self.before, &mut filters.before),
+            (&self.active_since, &mut filters.active_since),
+        ] {
+            let Some(selector) = value.as_deref().filter(|v| iteration_filter::is_iteration_selector(v)) else {
+                continue;
+            };
+            if iterations.is_none() {
+                iterations = Some(client.fetch_iterations(project_node_id, &iteration_field).await?);
+            }
+            let today = iteration_filter::today();
+            let start = iteration_filter::iteration_start(iterations.as_deref().unwrap_or_default(), selector, today)?;
+            *bound = Some(time_filter::local_midnight(start)?);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Args, Debug)]
+struct OpenArgs {
+    /// GitHub Project identifier (owner/number or GraphQL node ID). Defaults to the last-used project
+    project_id: Option<String>,
+
+    /// Project number under the owner of the current repository's `origin` remote
+    #[arg(long = "project-number", value_name = "N", conflicts_with = "project_id")]
+    project_number: Option<u32>,
+
+    #[command(flatten)]
+    filters: FilterArgs,
+
+    /// Refuse to open more than this many issues
+    #[arg(long = "max", value_name = "N", default_value_t = 20)]
+    max: usize,
+
+    /// Don't ask for confirmation before opening many issues
+    #[arg(short = 'y', long = "yes")]
+    yes: bool,
+}
+
+#[derive(Args, Debug)]
+struct DiffArgs {
+    /// GitHub Project identifier (owner/number or GraphQL node ID). Defaults to the last-used project
+    project_id: Option<String>,
+
+    /// Project number under the owner of the current repository's `origin` remote
+    #[arg(long = "project-number", value_name = "N", conflicts_with = "project_id")]
+    project_number: Option<u32>,
+
+    /// Iteration to compare against (same syntax as --iteration)
+    #[arg(long = "previous", value_name = "ITERATION", default_value = "@previous")]
+    previous: String,
+
+    /// Iteration to report on (same syntax as --iteration)
+    #[arg(long = "current", value_name = "ITERATION", default_value = "@current")]
+    current: String,
+
+    #[command(flatten)]
+    filters: FilterArgs,
+
+    /// Render the comparison as markdown
+    #[arg(long = "markdown")]
+    markdown: bool,
+}
+
+#[derive(Args, Debug, Default)]
+struct SummarizeArgs {
+    /// GitHub Project identifiers (owner/number or GraphQL node ID); issues from several boards are
+    /// combined into one report. Defaults to the last-used project
+    #[arg(value_name = "PROJECT_ID")]
+    project_ids: Vec<String>,
+
+    /// Project number under the owner of the current repository's `origin` remote
+    #[arg(long = "project-number", value_name = "N", conflicts_with = "project_ids")]
+    project_number: Option<u32>,
+
+    #[command(flatten)]
+    filters: FilterArgs,
+
+    /// Include draft items from the board in a "Drafts" section
+    #[arg(long = "include-drafts")]
+    include_drafts: bool,
+
+    /// Collapse issues linked as tracked-by or duplicates into one entry listing every repository
+    #[arg(long = "merge-linked")]
+    merge_linked: bool,
+
+    /// Print nothing (and exit successfully) when fewer than this many issues match
+    #[arg(long = "min-issues", value_name = "N")]
+    min_issues: Option<usize>,
+
+    /// Stop fetching once this many issues match, e.g. while iterating on a template or prompt
+    #[arg(long = "sample", value_name = "N")]
+    sample: Option<std::num::NonZeroUsize>,
+
+    /// Use the time stored in this file by the previous run as --since, and record this run's
+    /// start time there on success. Falls back to --since when the file doesn't exist yet
+    #[arg(long = "since-file", value_name = "PATH")]
+    since_file: Option<PathBuf>,
+
+    /// Only include issues closed since this tag was released (its GitHub Release, else its commit).
+    /// The repository comes from --release-repo or the `origin` remote
+    #[arg(long = "since-tag", value_name = "TAG", conflicts_with_all = ["since", "since_file"])]
+    since_tag: Option<String>,
+
+    /// Exit with an error when no issues match, e.g. to catch a broken query in CI
+    #[arg(long = "fail-on-empty")]
+    fail_on_empty: bool,
+
+    /// Print only the number of matching issues
+    #[arg(long = "count-only", conflicts_with_all = ["ai", "output", "output_dir", "min_issues", "sample"])]
+    count_only: bool,
+
+    /// Output format
+    #[arg(short = 'f', long = "format", value_enum, default_value = "text")]
+    format: OutputFormat,
+
+    /// Group issues by parent issue (shorthand for --group-by parent)
+    #[arg(short = 'w', long = "wrap", conflicts_with = "group_by")]
+    wrap: bool,
+
+    /// Group issues by parent, repository, milestone, or a single-select field (field:<Name>)
+    #[arg(long = "group-by", value_name = "GROUPING")]
+    group_by: Option<GroupBy>,
+
+    /// Wrap each group in a collapsible <details> block (markdown with --wrap)
+    #[arg(long = "collapsible")]
+    collapsible: bool,
+
+    /// Only list parent issues with their number of completed children
+    #[arg(long = "parent-only")]
+    parent_only: bool,
+
+    /// Where issues without a parent go when grouping by parent
+    #[arg(long = "standalone", value_enum, value_name = "PLACEMENT", default_value = "last")]
+    standalone: StandalonePlacement,
+
+    /// Remove emoji and :shortcode: forms from issue titles
+    #[arg(long = "strip-emoji")]
+    strip_emoji: bool,
+
+    /// Print one line per issue in text output: `#123 title — repo (closed date)`
+    #[arg(long = "compact")]
+    compact: bool,
+
+    /// Format close dates and counts for this locale (e.g., de-DE, en-US)
+    #[arg(long = "locale", value_name = "TAG")]
+    locale: Option<String>,
+
+    /// strftime pattern for close dates, e.g. "%d %b %Y" (overrides the --locale default)
+    #[arg(long = "date-format", value_name = "FORMAT")]
+    date_format: Option<String>,
+
+    /// Truncate titles in text and table output to this many characters
+    #[arg(long = "max-title-width", value_name = "N")]
+    max_title_width: Option<usize>,
+
+    /// Show how long each issue took to close
+    #[arg(long = "show-age")]
+    show_age: bool,
+
+    /// Show each issue's number of comments
+    #[arg(long = "show-comments")]
+    show_comments: bool,
+
+    /// Order issues by this key instead of the board order
+    #[arg(long = "sort", value_enum, default_value = "board")]
+    sort: SortKey,
+
+    /// Report median and max time to close in the header
+    #[arg(long = "stats")]
+    stats: bool,
+
+    /// Omit the count header and emit just the issues
+    #[arg(long = "no-header")]
+    no_header: bool,
+
+    /// Render markdown links as numbered footnote-style references at the end
+    #[arg(long = "render-links-as-footnotes")]
+    render_links_as_footnotes: bool,
+
+    /// When to use colors in text output
+    #[arg(long = "color", value_enum, default_value = "auto")]
+    color: ColorChoice,
+
+    /// Append a footer counting issues per repository (text and markdown)
+    #[arg(long = "repos-summary")]
+    repos_summary: bool,
+
+    /// Make issue numbers in text output clickable (OSC 8) instead of printing URLs; only on a terminal
+    #[arg(long = "hyperlinks")]
+    hyperlinks: bool,
+
+    /// Render output with a built-in theme (see `doner themes`)
+    #[arg(long = "theme", conflicts_with = "template")]
+    theme: Option<String>,
+
+    /// Render output with a custom template file
+    #[arg(long = "template")]
+    template: Option<String>,
+
+    /// Extra template variable as name=value (repeatable; requires --template or --theme)
+    #[arg(long = "template-var", value_name = "NAME=VALUE")]
+    template_vars: Vec<String>,
+
+    /// Use AI to generate a rich summary (requires OPENAI_API_KEY or ANTHROPIC_API_KEY)
+    #[arg(long = "ai")]
+    ai: bool,
+
+    /// Extra instructions appended to the AI prompt, e.g. "be concise, max 5 bullets"
+    #[arg(long = "prompt-append", value_name = "TEXT", requires = "ai")]
+    prompt_append: Option<String>,
+
+    /// Summarize issue lists longer than this many characters in batches, then merge the batch
+    /// summaries (0 sends everything in one call)
+    #[arg(long = "ai-chunk-size", value_name = "CHARS", requires = "ai", default_value_t = llm::DEFAULT_CHUNK_CHARS)]
+    ai_chunk_size: usize,
+
+    /// Write the report to a file instead of stdout
+    #[arg(short = 'o', long = "output")]
+    output: Option<PathBuf>,
+
+    /// Append to the --output file below a dated separator instead of overwriting it
+    #[arg(long = "append", requires = "output")]
+    append: bool,
+
+    /// Write one file per parent issue (plus standalone issues) into this directory
+    #[arg(long = "output-dir", conflicts_with_all = ["output", "ai", "template", "theme"])]
+    output_dir: Option<PathBuf>,
+
+    /// Create a draft GitHub Release for this tag with the report as its notes
+    #[arg(long = "publish-release", value_name = "TAG", conflicts_with_all = ["count_only", "output_dir"])]
+    publish_release: Option<String>,
+
+    /// Repository (owner/name) for --publish-release and --since-tag. Defaults to the `origin` remote's repository
+    #[arg(long = "release-repo", value_name = "OWNER/REPO")]
+    release_repo: Option<String>,
+
+    /// Email the report (HTML with a plaintext fallback) to this address; repeatable. SMTP is set via DONER_SMTP_*
+    #[arg(long = "email-to", value_name = "ADDR", conflicts_with_all = ["count_only", "output_dir"])]
+    email_to: Vec<String>,
+
+    /// POST the report to this webhook URL as JSON (Slack-compatible {"text": ...} by default)
+    #[arg(long = "post-url", value_name = "URL", conflicts_with_all = ["count_only", "output_dir"])]
+    post_url: Option<String>,
+
+    /// Payload template file for --post-url; rendered with `text` and must produce JSON
+    #[arg(long = "post-template", value_name = "FILE", requires = "post_url")]
+    post_template: Option<PathBuf>,
+
+    /// Log the GraphQL rate-limit cost of each page fetched
+    #[arg(short = 'v', long = "verbose")]
+    verbose: bool,
+
+    /// Show debug information about fetched items
+    #[arg(long = "debug")]
+    debug: bool,
+
+    /// List the archived items that were skipped (title, column, URL) on stderr after the report
+    #[arg(long = "show-archived", conflicts_with_all = ["include_archived", "only_archived"])]
+    show_archived: bool,
+
+    /// Don't print the one-line fetch summary to stderr
+    #[arg(short = 'q', long = "quiet")]
+    quiet: bool,
+}
+
+#[derive(Args, Debug)]
+struct DigestArgs {
+    /// GitHub Project identifier (owner/number or GraphQL node ID). Defaults to the last-used project
+    project_id: Option<String>,
+
+    /// Column name to fetch issues from
+    #[arg(short = 'c', long = "col", default_value = "Done")]
+    column: String,
+
+    /// Filter issues by time (e.g., 7d, 24h, yesterday, this-week)
+    #[arg(short = 's', long = "since", default_value = "this-week")]
+    since: String,
+
+    /// Filter by iteration (e.g., @current, @previous, @current,@previous, or iteration name)
+    #[arg(short = 'i', long = "iteration")]
+    iteration: Option<String>,
+
+    /// Output format
+    #[arg(short = 'f', long = "format", value_enum, default_value = "markdown")]
+    format: OutputFormat,
+
+    /// Do not group issues by parent issue (only applies with --no-ai)
+    #[arg(long = "no-wrap")]
+    no_wrap: bool,
+
+    /// Skip the AI summary and print the issue list
+    #[arg(long = "no-ai")]
+    no_ai: bool,
+
+    /// Show debug information about fetched items
+    #[arg(long = "debug")]
+    debug: bool,
+}
+
+impl From<DigestArgs> for SummarizeArgs {
+    fn from(args: DigestArgs) -> Self {
+        Self {
+            project_ids: args.project_id.into_iter().collect(),
+            filters: FilterArgs {
+                column: args.column,
+                since: Some(args.since),
+                iteration: args.iteration,
+                ..Default::default()
+            },
+            format: args.format,
+            // Grouping only affects the plain issue list
+            wrap: args.no_ai && !args.no_wrap,
+            ai: !args.no_ai,
+            ai_chunk_size: llm::DEFAULT_CHUNK_CHARS,
+            debug: args.debug,
+            ..Default::default()
+        }
+    }
+}
+
+impl SummarizeArgs {
+    /// Build the rendering options from the parsed flags
+    fn output_options(&self) -> Result<OutputOptions> {
+        if self.ai && self.format == OutputFormat::Urls {
+            return Err(anyhow!("--ai cannot be used with --format urls: bare URLs give the AI nothing to summarize"));
+        }
+
+        let locale = self.locale.as_deref().map(locale::parse_locale).transpose()?;
+        if let Some(format) = &self.date_format {
+            output::validate_date_format(format)?;
+        }
+        let mut options = OutputOptions {
+            format: self.format,
+            date_format: match (&self.date_format, locale) {
+                (Some(format), _) => format.clone(),
+                (None, Some(locale)) => locale.date_format.to_string(),
+                (None, None) => output::DEFAULT_DATE_FORMAT.to_string(),
+            },
+            thousands_separator: locale.map(|locale| locale.thousands_separator),
+            group_by: match &self.group_by {
+                Some(group_by) => group_by.clone(),
+                None if self.wrap => GroupBy::Parent,
+                None => GroupBy::None,
+            },
+            // Never send escape codes to the LLM
+            color: !self.ai && self.color.enabled(),
+            hyperlinks: !self.ai && self.hyperlinks && self.color.hyperlinks(),
+            collapsible: self.collapsible,
+            parent_only: self.parent_only,
+            standalone: self.standalone,
+            strip_emoji: self.strip_emoji,
+            compact: self.compact,
+            max_title_width: self.max_title_width,
+            show_age: self.show_age,
+            show_comments: self.show_comments,
+            show_stats: self.stats,
+            no_header: self.no_header,
+            link_footnotes: self.render_links_as_footnotes,
+            repos_summary: self.repos_summary,
+            ..OutputOptions::default()
+        };
+
+        // Allow overriding the changelog label mapping via environment variable
+        if let Ok(mapping) = std::env::var("DONER_CHANGELOG_SECTIONS") {
+            options.changelog_sections = output::parse_changelog_sections(&mapping)?;
+        }
+
+        Ok(options)
+    }
+
+    /// Resolve the template source from --theme or --template, if any
+    fn template_source(&self) -> Result<Option<String>> {
+        if let Some(theme) = &self.theme {
+            return Ok(Some(template::builtin_theme(theme)?.to_string()));
+        }
+        self.template
+            .as_deref()
+            .map(template::load_template_file)
+            .transpose()
+    }
+
+    /// Parse the --template-var entries
+    fn template_vars(&self) -> Result<Vec<(String, String)>> {
+        if !self.template_vars.is_empty() && self.template.is_none() && self.theme.is_none() {
+            return Err(anyhow!("--template-var requires --template or --theme"));
+        }
+        self.template_vars.iter().map(|entry| template::parse_template_var(entry)).collect()
+    }
+}
+
 #[derive(Subcommand, Debug)]
 enum AuthAction {
     /// Log in to GitHub (interactive)
@@ -85,25 +637,103 @@ enum AuthAction {
     Logout,
 
     /// Check authentication status
-    Status,
+    Status {
+        /// Print machine-readable JSON
+        #[arg(long = "json")]
+        json: bool,
+
+        /// Validate the token with GitHub even if its login is cached
+        #[arg(long = "refresh")]
+        refresh: bool,
+    },
+}
+
+/// Machine-readable output of `doner auth status --json`
+#[derive(Serialize)]
+struct AuthStatusJson {
+    authenticated: bool,
+    host: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    login: Option<String>,
+    /// The login was read from the cache instead of validated
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    cached: bool,
+}
+
+/// Load a .env file; variables already set in the environment take precedence
+fn load_env_file(path: Option<&Path>) -> Result<()> {
+    match path {
+        Some(path) => dotenvy::from_path(path)
+            .map_err(|e| anyhow!("Failed to load env file '{}': {}", path.display(), e)),
+        None => match dotenvy::dotenv() {
+            Ok(_) => Ok(()),
+            Err(e) if e.not_found() => Ok(()),
+            Err(e) => Err(anyhow!("Failed to load .env: {}", e)),
+        },
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // Must run before anything reads GITHUB_TOKEN or DONER_* variables
+    load_env_file(cli.env_file.as_deref())?;
+
+    if let Some(proxy) = cli.proxy {
+        http::set_proxy(proxy);
+    }
+
+    if let Some(api_url) = cli.api_url {
+        http::normalize_api_url(&api_url)?;
+        http::set_api_url(api_url);
+    }
+
+    if let (Some(app_id), Some(private_key_path), Some(installation_id)) =
+        (cli.app_id, cli.app_key, cli.app_installation_id)
+    {
+        app_auth::set_credentials(app_auth::AppCredentials {
+            app_id,
+            private_key_path,
+            installation_id,
+        });
+    }
+
     match cli.command {
         Commands::Auth { action } => handle_auth(action).await,
-        Commands::Summarize {
-            project_id,
-            column,
-            since,
-            iteration,
-            format,
-            wrap,
-            ai,
-            debug,
-        } => handle_summarize(project_id, column, since, iteration, format, wrap, ai, debug).await,
+        Commands::Summarize(args) => handle_summarize(*args).await,
+        Commands::Digest(args) => handle_summarize(args.into()).await,
+        Commands::Open(args) => handle_open(*args).await,
+        Commands::Diff(args) => handle_diff(*args).await,
+        Commands::Columns { project_id } => handle_columns(project_id).await,
+        Commands::Iterations { project_id } => handle_iterations(project_id).await,
+        Commands::Themes => {
+            for name in template::theme_names() {
+                println!("{}", name);
+            }
+            Ok(())
+        }
+        Commands::JsonSchema => {
+            println!("{}", serde_json::to_string_pretty(&doner::Issue::json_schema())?);
+            Ok(())
+        }
+        Commands::Version { verbose } => {
+            print_version(verbose);
+            Ok(())
+        }
+    }
+}
+
+/// Print the version, with build metadata for bug reports when verbose
+fn print_version(verbose: bool) {
+    println!("doner {}", env!("CARGO_PKG_VERSION"));
+    if verbose {
+        println!("commit: {}", env!("DONER_GIT_COMMIT"));
+        println!("built:  {}", env!("DONER_BUILD_DATE"));
+        println!("rustc:  {}", env!("DONER_RUSTC_VERSION"));
+        println!("target: {}", env!("DONER_TARGET"));
     }
 }
 
@@ -127,39 +757,99 @@ async fn handle_auth(action: AuthAction) -> Result<()> {
 
                 let user = auth::validate_token(&token).await?;
                 println!("OK");
+                if let Err(e) = auth::cache_login(&token, &user) {
+                    eprintln!("Warning: could not cache login: {}", e);
+                }
                 user
             };
 
             print!("Storing token... ");
             std::io::Write::flush(&mut std::io::stdout())?;
 
-            auth::store_token(&token)?;
-            println!("OK");
+            let store = auth::store_token(&token)?;
+            println!("OK ({})", store.name());
 
-            println!("Logged in as {}", username);
+            println!("Logged in to {} as {}", auth::active_host()?, username);
         }
 
         AuthAction::Logout => {
-            if auth::has_token() {
+            if let Some(store) = auth::token_store() {
                 auth::delete_token()?;
-                println!("Logged out. Token removed from keychain.");
+                if let Err(e) = auth::clear_cached_login() {
+                    eprintln!("Warning: could not clear cached login: {}", e);
+                }
+                println!("Logged out. Token removed from {}.", store.name());
             } else {
                 println!("Not logged in.");
             }
         }
 
-        AuthAction::Status => {
-            // Check environment variable first
-            if std::env::var("GITHUB_TOKEN").is_ok() {
+        AuthAction::Status { json: true, refresh } => {
+            // Installation tokens can't query the viewer; minting one proves the App credentials work
+            if app_auth::credentials()?.is_some() {
+                let status = AuthStatusJson {
+                    authenticated: auth::resolve_token().await.is_ok(),
+                    host: auth::active_host()?,
+                    source: Some("app"),
+                    login: None,
+                    cached: false,
+                };
+                println!("{}", serde_json::to_string(&status)?);
+                return Ok(());
+            }
+
+            let (source, token) = if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+                (Some("env"), Some(token))
+            } else if let Some(store) = auth::token_store() {
+                let source = match store {
+                    auth::TokenStore::Keychain => "keychain",
+                    auth::TokenStore::EncryptedFile => "file",
+                };
+                (Some(source), auth::get_token().ok())
+            } else {
+                (None, None)
+            };
+
+            let login = match &token {
+                Some(token) => auth::login_for_token(token, refresh).await.ok(),
+                None => None,
+            };
+
+            let status = AuthStatusJson {
+                authenticated: login.is_some(),
+                host: auth::active_host()?,
+                source,
+                cached: login.as_ref().is_some_and(|(_, cached)| *cached),
+                login: login.map(|(login, _)| login),
+            };
+            println!("{}", serde_json::to_string(&status)?);
+        }
+
+        AuthAction::Status { json: false, refresh } => {
+            if let Some(credentials) = app_auth::credentials()? {
+                match auth::resolve_token().await {
+                    Ok(_) => println!(
+                        "Using GitHub App {} (installation {})",
+                        credentials.app_id, credentials.installation_id
+                    ),
+                    Err(e) => println!("GitHub App credentials are configured but not working: {:#}", e),
+                }
+            } else if std::env::var("GITHUB_TOKEN").is_ok() {
                 println!("Using token from GITHUB_TOKEN environment variable");
-            } else if auth::has_token() {
+            } else if let Some(store) = auth::token_store() {
                 let token = auth::get_token()?;
-                match auth::validate_token(&token).await {
-                    Ok(username) => {
-                        println!("Logged in as {} (token stored in keychain)", username);
+                match auth::login_for_token(&token, refresh).await {
+                    Ok((username, cached)) => {
+                        println!(
+                            "Logged in to {} as {} (token stored in {}{})",
+                            auth::active_host()?,
+                            username,
+                            store.name(),
+                            if cached { "; cached, use --refresh to re-validate" } else { "" }
+                        );
                     }
                     Err(_) => {
-                        println!("Token found in keychain but appears invalid or expired.");
+                        println!("Token found in {} but appears invalid or expired.", store.name());
                         println!("Run 'doner auth login' to re-authenticate.");
                     }
                 }
@@ -173,45 +863,441 @@ async fn handle_auth(action: AuthAction) -> Result<()> {
     Ok(())
 }
 
-async fn handle_summarize(
-    project_id: String,
-    column: String,
-    since: Option<String>,
-    iteration: Option<String>,
-    format: OutputFormat,
-    wrap: bool,
-    ai: bool,
-    debug: bool,
-) -> Result<()> {
-    let token = auth::resolve_token()?;
+/// Ask before opening more than this many browser tabs
+const OPEN_CONFIRM_THRESHOLD: usize = 5;
+
+async fn handle_open(args: OpenArgs) -> Result<()> {
+    let mut filters = args.filters.issue_filters()?;
+
+    let client = with_reauth(github::GitHubClient::from_env(auth::resolve_token).await?)?;
+    let project_id = resolve_project_arg(&client, args.project_id, args.project_number).await?;
+    let project = client.resolve_project_id(&project_id).await?;
+    args.filters.resolve_iteration_bounds(&client, &project.node_id, &mut filters).await?;
+    let (issues, _) = client.fetch_project_issues(&project.node_id, &filters, false).await?;
+
+    if issues.is_empty() {
+        println!("No issues found in column \"{}\"", filters.column);
+        return Ok(());
+    }
+    if issues.len() > args.max {
+        return Err(anyhow!(
+            "{} issues match, more than --max {}. Narrow the filters or raise --max",
+            issues.len(),
+            args.max
+        ));
+    }
+    if issues.len() > OPEN_CONFIRM_THRESHOLD && !args.yes && !confirm_open(issues.len())? {
+        return Ok(());
+    }
+
+    for issue in &issues {
+        open::that(&issue.url).with_context(|| format!("Failed to open {}", issue.url))?;
+    }
+    eprintln!("Opened {} issue(s)", issues.len());
+
+    Ok(())
+}
+
+async fn handle_diff(args: DiffArgs) -> Result<()> {
+    if args.filters.iteration.is_some() {
+        return Err(anyhow!("doner diff compares --previous with --current; use those instead of --iteration"));
+    }
+    let mut filters = args.filters.issue_filters()?;
+
+    let client = with_reauth(github::GitHubClient::from_env(auth::resolve_token).await?)?;
+    let project_id = resolve_project_arg(&client, args.project_id, args.project_number).await?;
+    let project = client.resolve_project_id(&project_id).await?;
+    args.filters.resolve_iteration_bounds(&client, &project.node_id, &mut filters).await?;
+
+    let window = |iteration: &str| github::IssueFilters {
+        iteration: Some(iteration.to_string()),
+        ..filters.clone()
+    };
+    let (previous, _) = client.fetch_project_issues(&project.node_id, &window(&args.previous), false).await?;
+    let (current, _) = client.fetch_project_issues(&project.node_id, &window(&args.current), false).await?;
+
+    let diff = diff::diff_issues(previous, current);
+    println!("{}", diff::format_diff(&diff, &args.previous, &args.current, args.markdown));
+    Ok(())
+}
+
+/// Ask whether to open `count` tabs; without a terminal, require --yes instead
+fn confirm_open(count: usize) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow!("Refusing to open {} issues without confirmation. Pass --yes to proceed", count));
+    }
 
-    let since_filter = since
-        .as_ref()
-        .map(|s| time_filter::parse_time_filter(s))
+    eprint!("Open {} issues in the browser? [y/N] ", count);
+    std::io::Write::flush(&mut std::io::stderr())?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// The skipped archived items for --show-archived, one per line
+fn archived_list(items: &[github::ArchivedItem]) -> String {
+    if items.is_empty() {
+        return "No archived items were skipped".to_string();
+    }
+
+    let mut list = format!("Archived items (skipped): {}", items.len());
+    for item in items {
+        list.push_str(&format!("\n  • [{}] {}", item.column.as_deref().unwrap_or("no status"), item.title));
+        if let Some(url) = &item.url {
+            list.push_str(&format!(" — {}", url));
+        }
+    }
+    list
+}
+
+/// Repository given with --release-repo, else the one of the `origin` remote
+fn release_repo_or_origin(release_repo: Option<&str>, flag: &str) -> Result<String> {
+    match release_repo {
+        Some(repo) => Ok(repo.to_string()),
+        None => git::origin_repo()
+            .ok_or_else(|| anyhow!("{} needs a GitHub 'origin' remote or --release-repo <owner/repo>", flag)),
+    }
+}
+
+/// Offer to log in again if the stored token is rejected mid-run. Only on a terminal, and only
+/// for stored tokens: App and GITHUB_TOKEN credentials can't be replaced by logging in.
+fn with_reauth(client: github::GitHubClient) -> Result<github::GitHubClient> {
+    if !std::io::stdin().is_terminal() || app_auth::credentials()?.is_some() || std::env::var("GITHUB_TOKEN").is_ok() {
+        return Ok(client);
+    }
+
+    Ok(client.on_auth_expired(Box::new(|| {
+        eprint!("GitHub rejected the stored token; it may have expired. Log in again and resume? [y/N] ");
+        std::io::Write::flush(&mut std::io::stderr())?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+            return Ok(None);
+        }
+
+        let token = auth::interactive_login()?;
+        let store = auth::store_token(&token)?;
+        eprintln!("Token stored in {}. Resuming...", store.name());
+        Ok(Some(token))
+    })))
+}
+
+async fn handle_columns(project_id: Option<String>) -> Result<()> {
+    let project_id = state::resolve_project(project_id)?;
+
+    let client = github::GitHubClient::from_env(auth::resolve_token).await?;
+    let project = client.resolve_project_id(&project_id).await?;
+
+    for column in client.fetch_status_options(&project.node_id).await? {
+        println!("{}", column);
+    }
+
+    Ok(())
+}
+
+async fn handle_iterations(project_id: Option<String>) -> Result<()> {
+    let project_id = state::resolve_project(project_id)?;
+
+    let client = github::GitHubClient::from_env(auth::resolve_token).await?;
+    let project = client.resolve_project_id(&project_id).await?;
+
+    let iterations = client.fetch_iterations(&project.node_id, &github::iteration_field_name()).await?;
+    if iterations.is_empty() {
+        println!("No iterations found.");
+        return Ok(());
+    }
+
+    let today = iteration_filter::today();
+    let statuses = iteration_filter::classify_iterations(&iterations, today);
+    let width = iterations.iter().map(|it| it.title.len()).max().unwrap_or(0);
+
+    for (iteration, status) in iterations.iter().zip(statuses) {
+        let marker = if status == Some(IterationStatus::Current) { "*" } else { " " };
+        let label = match status {
+            Some(IterationStatus::Previous) => "@previous",
+            Some(IterationStatus::Current) => "@current",
+            Some(IterationStatus::Next) => "next",
+            None => "",
+        };
+        println!(
+            "{} {:<width$}  {}  {:>3}d  {}",
+            marker,
+            iteration.title,
+            iteration.start_date,
+            iteration.duration,
+            label,
+            width = width
+        );
+    }
+
+    Ok(())
+}
+
+/// Resolve the project from the arguments, the last-used project, or the `origin` remote's owner
+async fn resolve_project_arg(
+    client: &github::GitHubClient,
+    project_id: Option<String>,
+    project_number: Option<u32>,
+) -> Result<String> {
+    if let Some(number) = project_number {
+        let owner = git::origin_owner().ok_or_else(|| {
+            anyhow!(
+                "--project-number needs a GitHub 'origin' remote to infer the owner.\n  \
+                 Pass the full project ID instead, e.g. 'doner summarize myorg/{}'",
+                number
+            )
+        })?;
+        return Ok(format!("{}/{}", owner, number));
+    }
+
+    let err = match state::resolve_project(project_id) {
+        Ok(project_id) => return Ok(project_id),
+        Err(e) => e,
+    };
+
+    // Nothing given or remembered: offer the remote owner's projects when someone can answer
+    match git::origin_owner() {
+        Some(owner) if std::io::stdin().is_terminal() => pick_project(client, &owner).await,
+        _ => Err(err),
+    }
+}
+
+/// Prompt for one of the owner's open projects
+async fn pick_project(client: &github::GitHubClient, owner: &str) -> Result<String> {
+    let projects = client.fetch_owner_projects(owner).await?;
+    if projects.is_empty() {
+        return Err(anyhow!(
+            "No open projects found for '{}'. Pass a project ID, e.g. 'doner summarize {}/5'",
+            owner,
+            owner
+        ));
+    }
+
+    eprintln!("Projects owned by {}:", owner);
+    for project in &projects {
+        eprintln!("  {:>4}  {}", project.number, project.title);
+    }
+    eprint!("Project number: ");
+    std::io::Write::flush(&mut std::io::stderr())?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    let number: u32 = answer
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("Invalid project number '{}'", answer.trim()))?;
+    if !projects.iter().any(|p| p.number == number) {
+        return Err(anyhow!("Project #{} is not one of {}'s open projects", number, owner));
+    }
+
+    Ok(format!("{}/{}", owner, number))
+}
+
+async fn handle_summarize(mut args: SummarizeArgs) -> Result<()> {
+    let Some(since_file) = args.since_file.take() else {
+        return run_summarize(args).await;
+    };
+
+    // Recorded before fetching so issues closed during the run are picked up next time
+    let started = chrono::Utc::now();
+    if let Some(last_run) = state::read_since_file(&since_file)? {
+        args.filters.since = Some(last_run.to_rfc3339());
+    }
+    run_summarize(args).await?;
+    state::write_since_file(&since_file, started)
+}
+
+async fn run_summarize(args: SummarizeArgs) -> Result<()> {
+    let mut options = args.output_options()?;
+    let template_source = args.template_source()?;
+    let template_vars = args.template_vars()?;
+    let SummarizeArgs {
+        project_ids,
+        project_number,
+        filters: filter_args,
+        include_drafts,
+        merge_linked,
+        min_issues,
+        sample,
+        since_tag,
+        fail_on_empty,
+        count_only,
+        output: output_path,
+        append,
+        output_dir,
+        ai,
+        sort,
+        prompt_append,
+        ai_chunk_size,
+        publish_release,
+        release_repo,
+        email_to,
+        post_url,
+        post_template,
+        verbose,
+        debug,
+        show_archived,
+        quiet,
+        ..
+    } = args;
+    // Check the SMTP settings before fetching so a missing variable fails fast
+    let smtp = (!email_to.is_empty()).then(email::SmtpConfig::from_env).transpose()?;
+    let post_template = post_template
+        .map(|path| {
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read payload template '{}'", path.display()))
+        })
         .transpose()?;
 
-    let client = github::GitHubClient::new(&token);
+    if release_repo.is_some() && publish_release.is_none() && since_tag.is_none() {
+        return Err(anyhow!("--release-repo only applies with --publish-release or --since-tag"));
+    }
 
-    // Resolve project ID (either direct node ID or owner/number format)
-    let project_node_id = client.resolve_project_id(&project_id).await?;
+    let mut filters = github::IssueFilters {
+        include_drafts,
+        include_links: merge_linked,
+        fields: options.group_by.field_name().map(str::to_string).into_iter().collect(),
+        sample: sample.map(|n| n.get()),
+        list_archived: show_archived,
+        ..filter_args.issue_filters()?
+    };
+    let client = with_reauth(github::GitHubClient::from_env(auth::resolve_token).await?.verbose(verbose))?;
+    if let Some(tag) = &since_tag {
+        let repo = release_repo_or_origin(release_repo.as_deref(), "--since-tag")?;
+        let released = client.tag_date(&repo, tag).await?;
+        if !quiet {
+            eprintln!("Since {} in {} ({})", tag, repo, released.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"));
+        }
+        filters.since = Some(released);
+    }
+    let project_ids = if project_ids.is_empty() {
+        vec![resolve_project_arg(&client, None, project_number).await?]
+    } else {
+        project_ids
+    };
 
-    let (issues, stats) = client
-        .fetch_project_issues(&project_node_id, &column, since_filter, iteration.as_deref(), debug)
-        .await?;
+    let mut issues = Vec::new();
+    let mut stats = github::FetchStats::default();
+    let mut project_node_ids = Vec::new();
+    let mut resolved_filters: Option<github::IssueFilters> = None;
+    for project_id in &project_ids {
+        if let Some(n) = filters.sample
+            && issues.len() >= n
+        {
+            stats.sampled = true;
+            break;
+        }
+
+        // Resolve project ID (either direct node ID or owner/number format)
+        let project = client.resolve_project_id(project_id).await?;
+        options.project_urls.extend(project.url);
+
+        // Iteration selectors like @current resolve against each board's own iterations
+        let mut project_filters = filters.clone();
+        project_filters.sample = filters.sample.map(|n| n - issues.len());
+        filter_args.resolve_iteration_bounds(&client, &project.node_id, &mut project_filters).await?;
+
+        let (project_issues, project_stats) = client
+            .fetch_project_issues(&project.node_id, &project_filters, debug)
+            .await?;
+        issues.extend(project_issues);
+        stats.absorb(project_stats);
+        project_node_ids.push(project.node_id);
+        // The report's window (AI context, --debug) spans every board's
+        match &mut resolved_filters {
+            Some(resolved) => resolved.widen_window(&project_filters),
+            None => resolved_filters = Some(project_filters),
+        }
+    }
+    let filters = resolved_filters.unwrap_or(filters);
+    if stats.sampled {
+        eprintln!(
+            "Note: stopped after the first {} matching issue(s) (--sample); results may be incomplete",
+            issues.len()
+        );
+    }
+
+    // An issue can sit on several of the boards
+    if project_ids.len() > 1 {
+        let (deduped, duplicates) = merge::dedupe_by_url(issues);
+        issues = deduped;
+        stats.duplicates += duplicates;
+    }
+
+    let FilterArgs {
+        column,
+        since,
+        before,
+        active_since,
+        iteration,
+        ..
+    } = filter_args;
+
+    if merge_linked {
+        let before = issues.len();
+        issues = merge::merge_linked(issues);
+        if debug {
+            eprintln!("Debug: Merged linked issues: {}", before - issues.len());
+        }
+    }
+
+    output::sort_issues(&mut issues, sort);
+
+    // Remember the project so it can be omitted next time
+    if let [project_id] = project_ids.as_slice()
+        && let Err(e) = state::save_last_project(project_id)
+    {
+        eprintln!("Warning: could not save last-used project: {}", e);
+    }
 
     if debug {
-        eprintln!("Debug: Project node ID: {}", project_node_id);
+        eprintln!("Debug: Project node ID: {}", project_node_ids.join(", "));
         eprintln!("Debug: Looking for column: \"{}\"", column);
-        eprintln!("Debug: Status field: \"{}\"", std::env::var("DONER_STATUS_FIELD").unwrap_or_else(|_| "Status".to_string()));
+        eprintln!("Debug: Status field: \"{}\"", filters.status_field());
         if let Some(ref iter) = iteration {
             eprintln!("Debug: Iteration filter: \"{}\"", iter);
         }
+        if iteration.is_some() && (filters.since.is_some() || filters.before.is_some()) {
+            eprintln!("Debug: Filter mode: {:?}", filters.mode);
+        }
         eprintln!("Debug: Total items fetched: {}", stats.total_items);
-        eprintln!("Debug: Archived items (skipped): {}", stats.archived);
+        eprintln!("Debug: Total items reported by GitHub: {}", stats.reported_total);
+        if filters.archived == github::ArchivedItems::Exclude {
+            eprintln!("Debug: Archived items (skipped): {}", stats.archived);
+        } else {
+            eprintln!("Debug: Archived items (included): {}", stats.archived);
+        }
+        if filters.archived == github::ArchivedItems::Only {
+            eprintln!("Debug: Active items (skipped): {}", stats.skipped_active);
+        }
         eprintln!("Debug: Wrong column (skipped): {}", stats.wrong_column);
         eprintln!("Debug: Not an issue (skipped): {}", stats.not_issue);
         eprintln!("Debug: Filtered by iteration (skipped): {}", stats.filtered_by_iteration);
-        eprintln!("Debug: Filtered by time (skipped): {}", stats.filtered_by_time);
+        eprintln!(
+            "Debug: Filtered by time (skipped): {} ({} without a timestamp)",
+            stats.filtered_by_time, stats.missing_timestamp
+        );
+        if filters.active_since.is_some() {
+            eprintln!("Debug: Filtered by activity (skipped): {}", stats.filtered_by_activity);
+        }
+        eprintln!("Debug: Duplicates (skipped): {}", stats.duplicates);
+        if let Some(ref login) = filters.closed_by {
+            eprintln!("Debug: Closed-by filter: \"{}\"", login);
+            eprintln!("Debug: Filtered by closer (skipped): {}", stats.filtered_by_closed_by);
+            eprintln!("Debug: Closing actor unknown (skipped): {}", stats.closed_by_unknown);
+        }
+        if filters.title_match.is_some() || filters.title_exclude.is_some() {
+            eprintln!("Debug: Filtered by title (skipped): {}", stats.filtered_by_title);
+        }
+        if filters.milestone_due_before.is_some() {
+            eprintln!("Debug: Filtered by milestone due date (skipped): {}", stats.filtered_by_milestone_due);
+        }
+        if filters.completed_only {
+            eprintln!("Debug: Not closed as completed (skipped): {}", stats.filtered_by_state_reason);
+        }
+        if filters.exclude_bots {
+            eprintln!("Debug: Opened by or assigned to bots (skipped): {}", stats.filtered_by_bot);
+        }
         eprintln!("Debug: Final count: {}", issues.len());
         if !stats.columns_seen.is_empty() {
             eprintln!("Debug: Columns seen: {:?}", stats.columns_seen);
@@ -222,33 +1308,156 @@ async fn handle_summarize(
         eprintln!();
     }
 
+    // --debug already printed the full breakdown
+    let footer = (!quiet && !debug).then(|| stats.summary(issues.len(), &column));
+    let archived = show_archived.then(|| archived_list(&stats.archived_items));
+    let print_footer = || {
+        if let Some(list) = &archived {
+            eprintln!("{}", list);
+        }
+        if let Some(line) = &footer {
+            eprintln!("{}", line);
+        }
+    };
+
+    // Drafts get their own section and aren't counted, matching the report header
+    let issue_count = output::issue_count(&issues);
+
+    if fail_on_empty && issue_count == 0 {
+        return Err(anyhow!(
+            "No issues found in column \"{}\" (--fail-on-empty). Check the project, column and filters",
+            column
+        ));
+    }
+
+    if count_only {
+        println!("{}", issue_count);
+        return Ok(());
+    }
+
+    // Stay silent so scheduled jobs can skip sending near-empty reports
+    if let Some(min) = min_issues
+        && issue_count < min
+    {
+        if debug {
+            eprintln!("Debug: {} issue(s) is below --min-issues {}, skipping output", issue_count, min);
+        }
+        return Ok(());
+    }
+
     if issues.is_empty() {
         println!("No issues found in column \"{}\"", column);
+        print_footer();
         return Ok(());
     }
 
-    // Always compute the formatted output
-    let output = if wrap {
-        output::format_grouped(&issues, format)
-    } else {
-        output::format_list(&issues, format)
+    if let Some(dir) = output_dir {
+        for path in output::write_parent_files(&dir, &issues, &options.plain())? {
+            eprintln!("Wrote {}", path.display());
+        }
+        print_footer();
+        return Ok(());
+    }
+
+    // Always compute the formatted output; the LLM gets a flat list so it can group freely.
+    // Only the terminal gets colors, so the report itself is rendered plain.
+    let plain = options.plain();
+    let output = match &template_source {
+        Some(source) => template::render(source, &issues, &column, &template_vars)?,
+        None if ai => {
+            if options.group_by != GroupBy::None || options.parent_only {
+                eprintln!("Note: grouping only applies without --ai; the AI receives a flat issue list");
+            }
+            let flat = OutputOptions {
+                group_by: GroupBy::None,
+                parent_only: false,
+                ..plain.clone()
+            };
+            output::format_issues(&issues, &flat)
+        }
+        None => output::format_issues(&issues, &plain),
     };
 
     // If AI flag is set, pass the formatted output to the LLM
-    if ai {
-        let llm_client = llm::LlmClient::from_env()?;
+    let report = if ai {
+        let context = llm::SummaryContext::from_issues(&project_ids.join(", "), &issues, filters.since);
+        let mut llm_client = llm::LlmClient::from_env()?
+            .with_context(context)
+            .with_chunk_size(ai_chunk_size);
+        if let Some(text) = prompt_append {
+            llm_client = llm_client.with_prompt_append(text);
+        }
 
         eprint!("Generating AI summary... ");
         std::io::Write::flush(&mut std::io::stderr())?;
 
         let summary = llm_client.summarize(&output).await?;
-        eprintln!("done");
+        if summary.parts > 1 {
+            eprintln!("done (via {}, in {} parts)", summary.provider, summary.parts);
+        } else {
+            eprintln!("done (via {})", summary.provider);
+        }
         eprintln!();
 
-        println!("{}", summary);
+        summary.text
     } else {
-        println!("{}", output);
+        output
+    };
+
+    if let Some(tag) = publish_release {
+        let repo = release_repo_or_origin(release_repo.as_deref(), "--publish-release")?;
+        let url = client.create_draft_release(&repo, &tag, &report).await?;
+        eprintln!("Created draft release: {}", url);
     }
 
+    if let Some(smtp) = smtp {
+        // AI and template output is already markdown; plain formats are re-rendered as markdown for the HTML part
+        let already_markdown = ai
+            || template_source.is_some()
+            || matches!(options.format, OutputFormat::Markdown | OutputFormat::Changelog | OutputFormat::GithubRelease);
+        let markdown = if already_markdown {
+            report.clone()
+        } else {
+            let markdown_options = OutputOptions {
+                format: OutputFormat::Markdown,
+                ..plain.clone()
+            };
+            output::format_issues(&issues, &markdown_options)
+        };
+        let subject = format!("doner report: {} issue(s) from '{}'", issue_count, column);
+        email::send_report(&smtp, &email_to, &subject, &report, &email::markdown_to_html(&markdown)).await?;
+        eprintln!("Emailed report to {}", email_to.join(", "));
+    }
+
+    if let Some(url) = post_url {
+        let payload = webhook::render_payload(&report, post_template.as_deref())?;
+        let status = webhook::post(&url, &payload).await?;
+        eprintln!("Posted report to webhook (HTTP {})", status);
+    }
+
+    let Some(path) = output_path else {
+        if !ai && template_source.is_none() && (options.color || options.hyperlinks) {
+            println!("{}", output::format_issues(&issues, &options));
+        } else {
+            println!("{}", report);
+        }
+        print_footer();
+        return Ok(());
+    };
+
+    let separator = append.then(|| {
+        let mut applied = vec![format!("column: {}", column)];
+        applied.extend(since.map(|s| format!("since: {}", s)));
+        applied.extend(since_tag.map(|tag| format!("since tag: {}", tag)));
+        applied.extend(before.map(|b| format!("before: {}", b)));
+        applied.extend(active_since.map(|s| format!("active since: {}", s)));
+        applied.extend(iteration.map(|i| format!("iteration: {}", i)));
+        applied.extend(filters.closed_by.map(|login| format!("closed by: {}", login)));
+        output::append_separator(chrono::Local::now(), &applied)
+    });
+    output::write_report(&path, &report, separator.as_deref())?;
+    eprintln!("Wrote report to {}", path.display());
+    print_footer();
+
     Ok(())
 }
@@ -0,0 +1,126 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{Issue, MergedIssue};
+
+/// Collapse issues linked by tracking or duplicate relationships into a single entry.
+/// The first issue of each linked group (in input order) is kept, and the others are
+/// listed in its `merged` field. Links to issues outside `issues` are ignored.
+pub fn merge_linked(issues: Vec<Issue>) -> Vec<Issue> {
+    let index: HashMap<&str, usize> = issues.iter().enumerate().map(|(i, issue)| (issue.id.as_str(), i)).collect();
+
+    // Union-find over positions; the lowest position is the root so input order decides the survivor
+    let mut roots: Vec<usize> = (0..issues.len()).collect();
+    fn find(roots: &mut [usize], i: usize) -> usize {
+        let mut root = i;
+        while roots[root] != root {
+            root = roots[root];
+        }
+        roots[i] = root;
+        root
+    }
+
+    for (i, issue) in issues.iter().enumerate() {
+        for id in &issue.linked_ids {
+            if let Some(&j) = index.get(id.as_str()) {
+                let (a, b) = (find(&mut roots, i), find(&mut roots, j));
+                roots[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let groups: Vec<usize> = (0..issues.len()).map(|i| find(&mut roots, i)).collect();
+    let mut kept: Vec<Issue> = Vec::new();
+    let mut position: HashMap<usize, usize> = HashMap::new();
+
+    for (issue, root) in issues.into_iter().zip(groups) {
+        match position.get(&root) {
+            Some(&at) => kept[at].merged.push(MergedIssue {
+                number: issue.number,
+                url: issue.url,
+                repository: issue.repository,
+            }),
+            None => {
+                position.insert(root, kept.len());
+                kept.push(issue);
+            }
+        }
+    }
+
+    kept
+}
+
+/// Drop issues whose URL was already seen, keeping the first; returns the kept issues and
+/// how many were dropped. Drafts have no URL and are always kept.
+pub fn dedupe_by_url(issues: Vec<Issue>) -> (Vec<Issue>, usize) {
+    let mut seen = HashSet::new();
+    let before = issues.len();
+    let kept: Vec<Issue> = issues
+        .into_iter()
+        .filter(|issue| issue.is_draft || seen.insert(issue.url.clone()))
+        .collect();
+    let dropped = before - kept.len();
+    (kept, dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(id: &str, repository: &str, number: u64, linked: &[&str]) -> Issue {
+        Issue {
+            id: id.to_string(),
+            number,
+            url: format!("https://github.com/{}/issues/{}", repository, number),
+            repository: repository.to_string(),
+            linked_ids: linked.iter().map(|id| id.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_linked_collapses_groups() {
+        let issues = vec![
+            issue("I_1", "myorg/web", 1, &[]),
+            issue("I_2", "myorg/api", 7, &["I_1"]),
+            issue("I_3", "myorg/web", 3, &[]),
+            // Linked transitively through I_2
+            issue("I_4", "myorg/docs", 9, &["I_2", "I_outside"]),
+        ];
+
+        let merged = merge_linked(issues);
+
+        let ids: Vec<&str> = merged.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["I_1", "I_3"]);
+        let repos: Vec<&str> = merged[0].merged.iter().map(|m| m.repository.as_str()).collect();
+        assert_eq!(repos, vec!["myorg/api", "myorg/docs"]);
+        assert!(merged[1].merged.is_empty());
+    }
+
+    #[test]
+    fn test_dedupe_by_url() {
+        let draft = Issue {
+            is_draft: true,
+            ..Default::default()
+        };
+        let issues = vec![
+            issue("I_1", "myorg/web", 1, &[]),
+            issue("I_2", "myorg/api", 1, &[]),
+            issue("I_1", "myorg/web", 1, &[]),
+            draft.clone(),
+            draft,
+        ];
+
+        let (kept, dropped) = dedupe_by_url(issues);
+        assert_eq!(dropped, 1);
+        assert_eq!(kept.len(), 4);
+        assert_eq!(kept[1].repository, "myorg/api");
+    }
+
+    #[test]
+    fn test_merge_linked_keeps_unlinked() {
+        let issues = vec![issue("I_1", "myorg/web", 1, &["I_9"]), issue("I_2", "myorg/web", 2, &[])];
+        let merged = merge_linked(issues);
+        assert_eq!(merged.len(), 2);
+        assert!(merged.iter().all(|i| i.merged.is_empty()));
+    }
+}
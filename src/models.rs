@@ -1,17 +1,84 @@
-use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-#[derive(Debug, Clone)]
+/// An issue (or draft item) from a project board column
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
 pub struct Issue {
+    /// GraphQL node ID; stable across renames and transfers, unlike `number`
+    pub id: String,
     pub number: u64,
     pub title: String,
     pub url: String,
+    pub created_at: Option<DateTime<Utc>>,
     pub closed_at: Option<DateTime<Utc>>,
+    /// Why the issue was closed; `None` while it is open
+    pub state_reason: Option<StateReason>,
+    /// Last time the issue itself changed (comments, edits, state)
+    pub updated_at: Option<DateTime<Utc>>,
     pub parent: Option<ParentIssue>,
     pub repository: String,
+    pub labels: Vec<String>,
+    /// Login of the user who opened the issue; bot accounts end in `[bot]`
+    pub author: Option<String>,
+    /// Login of the user who closed the issue, if known
+    pub closed_by: Option<String>,
+    /// Logins of the users assigned to the issue
+    pub assignees: Vec<String>,
+    /// Number of comments on the issue
+    pub comment_count: u64,
+    /// When the most recent comment was posted
+    pub last_comment_at: Option<DateTime<Utc>>,
+    /// Title of the milestone the issue belongs to
+    pub milestone: Option<String>,
+    /// Due date of the issue's milestone, if it has one
+    pub milestone_due_on: Option<DateTime<Utc>>,
+    /// Value of the project date field the time filters use (--date-field project:<Name>)
+    pub project_date: Option<NaiveDate>,
+    /// Draft item with no repository, number, or URL
+    pub is_draft: bool,
+    /// Node IDs of issues this one tracks, is tracked by, or duplicates (fetched for --merge-linked)
+    #[serde(skip)]
+    pub linked_ids: Vec<String>,
+    /// Linked issues collapsed into this entry by --merge-linked
+    pub merged: Vec<MergedIssue>,
+    /// Values of requested custom single-select fields, keyed by field name
+    pub fields: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone)]
+impl Issue {
+    /// JSON Schema of a serialized issue, for tools consuming doner's output
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(Issue)).expect("schema serializes to JSON")
+    }
+
+    /// How long the issue stayed open, when both timestamps are known
+    pub fn time_to_close(&self) -> Option<Duration> {
+        Some(self.closed_at? - self.created_at?)
+    }
+
+    /// Whether a bot account opened the issue or is assigned to it
+    pub fn involves_bot(&self) -> bool {
+        self.author.iter().chain(&self.assignees).any(|login| is_bot_login(login))
+    }
+}
+
+/// Bot logins carry a `[bot]` suffix, e.g. `dependabot[bot]`
+pub fn is_bot_login(login: &str) -> bool {
+    login.ends_with("[bot]")
+}
+
+/// A linked issue from another entry, listed alongside the issue it was merged into
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct MergedIssue {
+    pub number: u64,
+    pub url: String,
+    pub repository: String,
+}
+
+/// The parent of an issue in GitHub's sub-issue hierarchy
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct ParentIssue {
     #[allow(dead_code)]
     pub number: u64,
@@ -19,6 +86,65 @@ pub struct ParentIssue {
     pub url: String,
 }
 
+/// A project identifier resolved against the GitHub API
+#[derive(Debug, Clone)]
+pub struct ResolvedProject {
+    /// GraphQL node ID (starts with "PVT_")
+    pub node_id: String,
+    /// Permalink to the project board, when known
+    pub url: Option<String>,
+}
+
+/// A project listed under its owner
+#[derive(Debug, Clone)]
+pub struct OwnerProject {
+    pub number: u32,
+    pub title: String,
+}
+
+/// An iteration from a project's iteration field configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct Iteration {
+    pub title: String,
+    #[serde(rename = "startDate")]
+    pub start_date: NaiveDate,
+    /// Length of the iteration in days
+    pub duration: i64,
+}
+
+impl Iteration {
+    /// First day after the iteration ends
+    pub fn end_date(&self) -> NaiveDate {
+        self.start_date + Duration::days(self.duration)
+    }
+
+    /// Check if the given date falls within this iteration
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start_date <= date && date < self.end_date()
+    }
+}
+
+/// Why an issue was closed (or reopened), as reported by GitHub's `stateReason`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StateReason {
+    Completed,
+    NotPlanned,
+    Duplicate,
+    Reopened,
+    /// A reason added by GitHub after this version of doner
+    #[serde(other)]
+    Other,
+}
+
+/// Position of an iteration relative to today
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IterationStatus {
+    Previous,
+    Current,
+    Next,
+}
+
 // GraphQL response structures
 
 #[derive(Debug, Deserialize)]
@@ -30,11 +156,24 @@ pub struct GraphQLResponse<T> {
 #[derive(Debug, Deserialize)]
 pub struct GraphQLError {
     pub message: String,
+    /// Machine-readable error category, e.g. "FORBIDDEN" or "NOT_FOUND"
+    #[serde(rename = "type", default)]
+    pub error_type: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ProjectData {
     pub node: Option<ProjectNode>,
+    /// Only requested with --verbose
+    #[serde(rename = "rateLimit", default)]
+    pub rate_limit: Option<RateLimit>,
+}
+
+/// Cost of a query against the GraphQL rate-limit budget
+#[derive(Debug, Deserialize)]
+pub struct RateLimit {
+    pub cost: u32,
+    pub remaining: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,6 +183,8 @@ pub struct ProjectNode {
 
 #[derive(Debug, Deserialize)]
 pub struct ItemConnection {
+    #[serde(rename = "totalCount")]
+    pub total_count: usize,
     pub nodes: Vec<ProjectItem>,
     #[serde(rename = "pageInfo")]
     pub page_info: PageInfo,
@@ -66,6 +207,12 @@ pub struct ProjectItem {
     #[serde(rename = "fieldValueByName")]
     pub field_value_by_name: Option<FieldValue>,
     pub iteration: Option<IterationValue>,
+    /// Only requested when time filters use a project date field
+    #[serde(rename = "dateValue", default)]
+    pub date_value: Option<FieldValue>,
+    /// Requested custom fields, keyed by their `field<N>` query alias
+    #[serde(flatten)]
+    pub custom_fields: HashMap<String, Option<FieldValue>>,
     pub content: Option<ItemContent>,
 }
 
@@ -73,6 +220,7 @@ pub struct ProjectItem {
 #[serde(tag = "__typename")]
 pub enum FieldValue {
     ProjectV2ItemFieldSingleSelectValue { name: Option<String> },
+    ProjectV2ItemFieldDateValue { date: Option<NaiveDate> },
     #[serde(other)]
     Other,
 }
@@ -81,7 +229,14 @@ impl FieldValue {
     pub fn name(&self) -> Option<&str> {
         match self {
             FieldValue::ProjectV2ItemFieldSingleSelectValue { name } => name.as_deref(),
-            FieldValue::Other => None,
+            _ => None,
+        }
+    }
+
+    pub fn date(&self) -> Option<NaiveDate> {
+        match self {
+            FieldValue::ProjectV2ItemFieldDateValue { date } => *date,
+            _ => None,
         }
     }
 }
@@ -117,20 +272,83 @@ impl IterationValue {
 #[derive(Debug, Deserialize)]
 #[serde(tag = "__typename")]
 pub enum ItemContent {
-    Issue(IssueContent),
+    Issue(Box<IssueContent>),
+    DraftIssue(DraftIssueContent),
     #[serde(other)]
     Other,
 }
 
+/// A draft item that only exists on the board, without a repository issue
+#[derive(Debug, Deserialize)]
+pub struct DraftIssueContent {
+    pub id: String,
+    pub title: String,
+    #[allow(dead_code)]
+    pub body: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct IssueContent {
+    pub id: String,
     pub number: u64,
     pub title: String,
     pub url: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
     #[serde(rename = "closedAt")]
     pub closed_at: Option<DateTime<Utc>>,
+    #[serde(rename = "stateReason", default)]
+    pub state_reason: Option<StateReason>,
+    #[serde(rename = "updatedAt", default)]
+    pub updated_at: Option<DateTime<Utc>>,
     pub repository: RepositoryInfo,
+    #[serde(default)]
+    pub author: Option<Actor>,
     pub parent: Option<ParentIssueContent>,
+    pub labels: Option<LabelConnection>,
+    #[serde(default)]
+    pub assignees: Option<AssigneeConnection>,
+    #[serde(default)]
+    pub comments: Option<CommentConnection>,
+    pub milestone: Option<Milestone>,
+    #[serde(rename = "timelineItems")]
+    pub timeline_items: Option<ClosedEventConnection>,
+    /// Only requested with --merge-linked
+    #[serde(rename = "trackedInIssues", default)]
+    pub tracked_in_issues: Option<NodeIdConnection>,
+    /// Only requested with --merge-linked
+    #[serde(default)]
+    pub duplicates: Option<DuplicateEventConnection>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeIdConnection {
+    pub nodes: Vec<NodeId>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeId {
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicateEventConnection {
+    pub nodes: Vec<DuplicateEvent>,
+}
+
+/// A "marked as duplicate" event; either side may be a pull request or deleted
+#[derive(Debug, Deserialize)]
+pub struct DuplicateEvent {
+    #[serde(default)]
+    pub canonical: Option<OptionalNodeId>,
+    #[serde(default)]
+    pub duplicate: Option<OptionalNodeId>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OptionalNodeId {
+    #[serde(default)]
+    pub id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -139,9 +357,115 @@ pub struct RepositoryInfo {
     pub name_with_owner: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct Milestone {
+    pub title: String,
+    #[serde(rename = "dueOn", default)]
+    pub due_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LabelConnection {
+    pub nodes: Vec<Label>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Label {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssigneeConnection {
+    pub nodes: Vec<Actor>,
+}
+
+/// Comment count plus the latest comment
+#[derive(Debug, Deserialize)]
+pub struct CommentConnection {
+    #[serde(rename = "totalCount")]
+    pub total_count: u64,
+    #[serde(default)]
+    pub nodes: Vec<Comment>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Comment {
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClosedEventConnection {
+    pub nodes: Vec<ClosedEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClosedEvent {
+    #[serde(default)]
+    pub actor: Option<Actor>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Actor {
+    pub login: String,
+    /// Only requested where it matters, e.g. "Bot" for the issue author
+    #[serde(rename = "__typename", default)]
+    pub typename: Option<String>,
+}
+
+impl Actor {
+    /// Login as shown on GitHub; GraphQL omits the `[bot]` suffix of bot accounts
+    pub fn display_login(self) -> String {
+        if self.typename.as_deref() == Some("Bot") && !is_bot_login(&self.login) {
+            format!("{}[bot]", self.login)
+        } else {
+            self.login
+        }
+    }
+}
+
+impl IssueContent {
+    /// Node IDs of issues related through tracking or duplicate markers, excluding this one
+    pub fn linked_ids(&self) -> Vec<String> {
+        let tracked = self.tracked_in_issues.iter().flat_map(|c| &c.nodes).map(|n| n.id.clone());
+        let duplicates = self
+            .duplicates
+            .iter()
+            .flat_map(|c| &c.nodes)
+            .flat_map(|e| [&e.canonical, &e.duplicate])
+            .filter_map(|side| side.as_ref().and_then(|n| n.id.clone()));
+
+        let mut ids: Vec<String> = tracked.chain(duplicates).filter(|id| *id != self.id).collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ParentIssueContent {
     pub number: u64,
     pub title: String,
     pub url: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_json_schema() {
+        let schema = Issue::json_schema();
+        let properties = &schema["properties"];
+
+        assert_eq!(properties["closed_at"]["format"], "date-time");
+        assert_eq!(properties["parent"]["anyOf"][0]["$ref"], "#/$defs/ParentIssue");
+        assert!(schema["$defs"]["ParentIssue"]["properties"]["url"].is_object());
+        // Internal link data is never serialized
+        assert!(properties.get("linked_ids").is_none());
+
+        let required: Vec<&str> = schema["required"].as_array().unwrap().iter().filter_map(|v| v.as_str()).collect();
+        assert!(required.contains(&"number"));
+        assert!(required.contains(&"title"));
+    }
+}
@@ -1,42 +1,864 @@
-use std::collections::HashMap;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, Local};
+use regex::Regex;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use unicode_segmentation::UnicodeSegmentation;
 
-use crate::models::Issue;
+use crate::color::Styler;
+use crate::models::{Issue, MergedIssue};
 use crate::OutputFormat;
 
+/// Default format used when rendering closed timestamps
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Default label-to-section mapping used by the changelog format
+pub const DEFAULT_CHANGELOG_SECTIONS: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("feature", "Features"),
+    ("enhancement", "Features"),
+    ("fix", "Bug Fixes"),
+    ("bug", "Bug Fixes"),
+    ("chore", "Chores"),
+    ("maintenance", "Chores"),
+];
+
+/// How issues are grouped in the output
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum GroupBy {
+    #[default]
+    None,
+    Parent,
+    Repository,
+    Milestone,
+    /// A custom single-select project field, e.g. `field:Area`
+    Field(String),
+}
+
+impl GroupBy {
+    /// Name of the custom field to fetch, if grouping by one
+    pub fn field_name(&self) -> Option<&str> {
+        match self {
+            GroupBy::Field(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for GroupBy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "none" => Ok(GroupBy::None),
+            "parent" => Ok(GroupBy::Parent),
+            "repository" => Ok(GroupBy::Repository),
+            "milestone" => Ok(GroupBy::Milestone),
+            _ => match s.strip_prefix("field:").map(str::trim) {
+                Some(name) if !name.is_empty() => Ok(GroupBy::Field(name.to_string())),
+                _ => Err(anyhow!(
+                    "Invalid grouping '{}'. Use parent, repository, milestone, or field:<Name>",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+/// Where grouped output places the issues that have no parent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum StandalonePlacement {
+    /// Before the parent groups
+    First,
+    /// After the parent groups
+    #[default]
+    Last,
+    /// Leave them out
+    Hide,
+}
+
+/// Join the parent groups and the standalone section in the requested order
+fn arrange_standalone(parents: String, standalone: String, placement: StandalonePlacement) -> String {
+    match placement {
+        StandalonePlacement::First => standalone + &parents,
+        StandalonePlacement::Last => parents + &standalone,
+        StandalonePlacement::Hide => parents,
+    }
+}
+
+/// Order of the issues in the output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum SortKey {
+    /// Keep the order of the project board
+    #[default]
+    Board,
+    /// Most commented issues first
+    Comments,
+    /// Children of the same parent together (by parent title, then number); issues
+    /// without a parent last, by number
+    Parent,
+}
+
+/// Reorder issues by `key`; ties keep their board order
+pub fn sort_issues(issues: &mut [Issue], key: SortKey) {
+    match key {
+        SortKey::Board => {}
+        SortKey::Comments => issues.sort_by_key(|issue| std::cmp::Reverse(issue.comment_count)),
+        SortKey::Parent => issues.sort_by_cached_key(|issue| {
+            let parent = issue.parent.as_ref().map(|p| (p.title.clone(), p.url.clone()));
+            (parent.is_none(), parent, issue.number)
+        }),
+    }
+}
+
+/// Options controlling how issues are rendered
+#[derive(Debug, Clone)]
+pub struct OutputOptions {
+    pub format: OutputFormat,
+    pub group_by: GroupBy,
+    /// Report time-to-close statistics in the header
+    pub show_stats: bool,
+    pub date_format: String,
+    /// Ordered (label, section) pairs used by the changelog format
+    pub changelog_sections: Vec<(String, String)>,
+    /// Emit ANSI colors in text output
+    pub color: bool,
+    /// Wrap grouped markdown sections in collapsible `<details>` blocks
+    pub collapsible: bool,
+    /// Links to the project boards the issues came from, shown in the header
+    pub project_urls: Vec<String>,
+    /// Only list parent issues with a count of completed children
+    pub parent_only: bool,
+    /// Placement of the "Standalone Issues" section when grouping by parent
+    pub standalone: StandalonePlacement,
+    /// Remove emoji and `:shortcode:` forms from issue titles
+    pub strip_emoji: bool,
+    /// Collapse each issue to a single line in text output
+    pub compact: bool,
+    /// Truncate titles in text output to this many graphemes
+    pub max_title_width: Option<usize>,
+    /// Annotate issues with how long they took to close
+    pub show_age: bool,
+    /// Annotate issues with their number of comments
+    pub show_comments: bool,
+    /// Omit the header (count, board link and stats) and emit just the issues
+    pub no_header: bool,
+    /// Render markdown links as numbered references collected at the end
+    pub link_footnotes: bool,
+    /// Make issue references in text output clickable (OSC 8) instead of printing URLs
+    pub hyperlinks: bool,
+    /// Append a footer counting issues per repository (text and markdown)
+    pub repos_summary: bool,
+    /// Group the digits of header counts with this separator (from --locale)
+    pub thousands_separator: Option<char>,
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::default(),
+            group_by: GroupBy::default(),
+            show_stats: false,
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            changelog_sections: DEFAULT_CHANGELOG_SECTIONS
+                .iter()
+                .map(|(label, section)| (label.to_string(), section.to_string()))
+                .collect(),
+            color: false,
+            collapsible: false,
+            project_urls: Vec::new(),
+            parent_only: false,
+            standalone: StandalonePlacement::default(),
+            strip_emoji: false,
+            compact: false,
+            max_title_width: None,
+            show_age: false,
+            show_comments: false,
+            no_header: false,
+            link_footnotes: false,
+            hyperlinks: false,
+            repos_summary: false,
+            thousands_separator: None,
+        }
+    }
+}
+
+impl OutputOptions {
+    /// The same options without ANSI colors or terminal hyperlinks, for output that doesn't go to the terminal
+    pub fn plain(&self) -> Self {
+        Self {
+            color: false,
+            hyperlinks: false,
+            ..self.clone()
+        }
+    }
+}
+
+/// Date part of a strftime pattern, for one-line layouts (`--compact`, tables): everything before
+/// the first time specifier, e.g. `%d.%m.%Y` from `%d.%m.%Y %H:%M`. A pattern that starts with the
+/// time is used whole
+fn date_only_format(pattern: &str) -> &str {
+    const TIME_SPECIFIERS: &str = "HIklMSfpPRTXrs";
+    let mut chars = pattern.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        // Skip padding flags such as `%-I` or `%_H`
+        while chars.next_if(|(_, c)| matches!(c, '-' | '_' | '0')).is_some() {}
+        let Some((_, specifier)) = chars.next() else { break };
+        if TIME_SPECIFIERS.contains(specifier) {
+            let date = pattern[..start].trim_end_matches(|c: char| c.is_whitespace() || matches!(c, ',' | 'T' | '@'));
+            return if date.is_empty() { pattern } else { date };
+        }
+    }
+    pattern
+}
+
+/// Reject strftime patterns chrono can't render, which would otherwise panic mid-output
+pub fn validate_date_format(pattern: &str) -> Result<()> {
+    if chrono::format::StrftimeItems::new(pattern).any(|item| matches!(item, chrono::format::Item::Error)) {
+        return Err(anyhow!("Invalid --date-format '{}'. Use strftime specifiers such as %Y-%m-%d", pattern));
+    }
+    Ok(())
+}
+
+/// A count for headers, with digits grouped per the locale
+fn count(n: usize, options: &OutputOptions) -> String {
+    match options.thousands_separator {
+        Some(separator) => crate::locale::group_digits(n, separator),
+        None => n.to_string(),
+    }
+}
+
+/// Parse a changelog mapping such as "feat=Features,fix=Bug Fixes"
+pub fn parse_changelog_sections(input: &str) -> Result<Vec<(String, String)>> {
+    input
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|pair| {
+            let (label, section) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Invalid changelog mapping '{}'. Expected 'label=Section'", pair))?;
+            Ok((label.trim().to_lowercase(), section.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Format issues according to the grouping selected in the options.
+/// Draft items are listed separately in a trailing "Drafts" section.
+pub fn format_issues(issues: &[Issue], options: &OutputOptions) -> String {
+    let mut output = if !issues.iter().any(|issue| issue.is_draft) {
+        format_board_issues(issues, options)
+    } else {
+        let (drafts, issues): (Vec<Issue>, Vec<Issue>) = issues.iter().cloned().partition(|issue| issue.is_draft);
+        let mut output = format_board_issues(&issues, options);
+        push_drafts_section(&mut output, &drafts, options);
+        output
+    };
+
+    if options.repos_summary {
+        push_repos_summary(&mut output, issues, options);
+    }
+    output
+}
+
+/// Number of issues the headers report. Drafts are listed in their own section and not counted,
+/// so --count-only, --min-issues and --fail-on-empty should use this too
+pub fn issue_count(issues: &[Issue]) -> usize {
+    issues.iter().filter(|issue| !issue.is_draft).count()
+}
+
+/// Issue counts per repository, most issues first (ties by name); drafts have no repository
+pub fn repository_counts(issues: &[Issue]) -> Vec<(&str, usize)> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for issue in issues.iter().filter(|issue| !issue.is_draft) {
+        match counts.iter_mut().find(|(repo, _)| *repo == issue.repository) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((&issue.repository, 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    counts
+}
+
+/// Append the `Repos: myorg/api (5), myorg/web (3)` footer for --repos-summary
+fn push_repos_summary(output: &mut String, issues: &[Issue], options: &OutputOptions) {
+    let counts = repository_counts(issues);
+    if counts.is_empty() {
+        return;
+    }
+    let list = counts
+        .iter()
+        .map(|(repo, count)| format!("{} ({})", repo, count))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match options.format {
+        OutputFormat::Text | OutputFormat::Table => {
+            output.push_str(&format!("\n\n{} {}", styler(options).bold("Repos:"), list))
+        }
+        OutputFormat::Markdown => output.push_str(&format!("\n\n**Repos:** {}", list)),
+        _ => {}
+    }
+}
+
+fn format_board_issues(issues: &[Issue], options: &OutputOptions) -> String {
+    let output = if options.parent_only
+        && !matches!(
+            options.format,
+            OutputFormat::Changelog | OutputFormat::GithubRelease | OutputFormat::Mermaid
+        )
+    {
+        format_parent_summary(issues, options)
+    } else {
+        match &options.group_by {
+            GroupBy::None => format_list(issues, options),
+            GroupBy::Parent => format_grouped(issues, options),
+            GroupBy::Repository => format_by_repository(issues, options),
+            GroupBy::Milestone => format_by_milestone(issues, options),
+            GroupBy::Field(name) => format_by_field(issues, name, options),
+        }
+    };
+
+    let markdown = matches!(
+        options.format,
+        OutputFormat::Markdown | OutputFormat::Changelog | OutputFormat::GithubRelease
+    );
+    if options.link_footnotes && markdown {
+        reference_links(&output)
+    } else {
+        output
+    }
+}
+
+/// Rewrite inline `[text](url)` links as `[text][n]`, with one `[n]: url` definition per
+/// unique URL appended at the end
+pub fn reference_links(markdown: &str) -> String {
+    static INLINE_LINK: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\(([^)\s]+)\)").expect("valid link pattern"));
+
+    let mut urls: Vec<String> = Vec::new();
+    let body = INLINE_LINK.replace_all(markdown, |caps: &regex::Captures| {
+        let url = &caps[2];
+        let n = match urls.iter().position(|u| u == url) {
+            Some(i) => i + 1,
+            None => {
+                urls.push(url.to_string());
+                urls.len()
+            }
+        };
+        format!("[{}][{}]", &caps[1], n)
+    });
+
+    if urls.is_empty() {
+        return body.into_owned();
+    }
+
+    let definitions: Vec<String> = urls.iter().enumerate().map(|(i, url)| format!("[{}]: {}", i + 1, url)).collect();
+    format!("{}\n\n{}", body, definitions.join("\n"))
+}
+
 /// Format issues as a simple list
-pub fn format_list(issues: &[Issue], format: OutputFormat) -> String {
-    match format {
-        OutputFormat::Text => format_list_text(issues),
-        OutputFormat::Markdown => format_list_markdown(issues),
+pub fn format_list(issues: &[Issue], options: &OutputOptions) -> String {
+    match options.format {
+        OutputFormat::Text => format_list_text(issues, options),
+        OutputFormat::Markdown => format_list_markdown(issues, options),
+        OutputFormat::Changelog => format_changelog(issues, options),
+        OutputFormat::GithubRelease => format_github_release(issues, options),
+        OutputFormat::Org => format_list_org(issues, options),
+        OutputFormat::Urls => format_list_urls(issues),
+        OutputFormat::Mermaid => format_mermaid(issues),
+        OutputFormat::Table => format_table(issues, options),
     }
 }
 
 /// Format issues grouped by parent
-pub fn format_grouped(issues: &[Issue], format: OutputFormat) -> String {
+pub fn format_grouped(issues: &[Issue], options: &OutputOptions) -> String {
+    match options.format {
+        OutputFormat::Text => format_grouped_text(issues, options),
+        OutputFormat::Markdown => format_grouped_markdown(issues, options),
+        // The changelog is already sectioned by label
+        OutputFormat::Changelog => format_changelog(issues, options),
+        OutputFormat::GithubRelease => format_github_release(issues, options),
+        OutputFormat::Org => format_grouped_org(issues, options),
+        OutputFormat::Urls => format_grouped_urls(issues, options.standalone),
+        OutputFormat::Mermaid => format_mermaid(issues),
+        // The Parent column already shows the grouping
+        OutputFormat::Table => format_table(issues, options),
+    }
+}
+
+/// Format one line per parent issue with its number of completed children
+pub fn format_parent_summary(issues: &[Issue], options: &OutputOptions) -> String {
+    let style = styler(options);
+    let grouped = group_by_parent(issues);
+    if options.format == OutputFormat::Urls {
+        return parent_urls(&grouped).join("\n");
+    }
+    let markdown = options.format == OutputFormat::Markdown;
+    let mut output = String::new();
+
+    if markdown {
+        push_markdown_header(&mut output, issues, options);
+    } else {
+        push_text_header(&mut output, issues, options);
+    }
+
+    let mut parents: Vec<_> = grouped.with_parent.iter().collect();
+    parents.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut lines = String::new();
+    for (parent_title, (parent_info, children)) in parents {
+        let url = parent_info.as_ref().map(|info| info.url.as_str()).filter(|url| !url.is_empty());
+        let line = match (markdown, url) {
+            (true, Some(url)) => format!("- [{}]({}) ({} completed)", parent_title, url, children.len()),
+            (true, None) => format!("- {} ({} completed)", parent_title, children.len()),
+            (false, _) => format!("• {} ({} completed)", style.bold(parent_title), children.len()),
+        };
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+
+    let mut standalone = String::new();
+    if !grouped.orphans.is_empty() {
+        let bullet = if markdown { "-" } else { "•" };
+        standalone.push_str(&format!("{} {} standalone issues\n", bullet, grouped.orphans.len()));
+    }
+
+    output.push_str(&arrange_standalone(lines, standalone, options.standalone));
+    output.trim_end().to_string()
+}
+
+/// Issue title as displayed, with emoji removed and text truncated when requested
+fn display_title<'a>(issue: &'a Issue, options: &OutputOptions) -> Cow<'a, str> {
+    let title = if options.strip_emoji {
+        Cow::Owned(strip_emoji(&issue.title))
+    } else {
+        Cow::Borrowed(issue.title.as_str())
+    };
+
+    // Only plain text is meant for narrow terminals
+    match options.max_title_width {
+        Some(width) if options.format == OutputFormat::Text => match truncate_graphemes(&title, width) {
+            Some(truncated) => Cow::Owned(truncated),
+            None => title,
+        },
+        _ => title,
+    }
+}
+
+/// Shorten text to at most `width` graphemes, ending in an ellipsis.
+/// Returns `None` when the text already fits.
+pub fn truncate_graphemes(text: &str, width: usize) -> Option<String> {
+    let mut graphemes = text.grapheme_indices(true);
+    let (cut, _) = graphemes.nth(width.saturating_sub(1))?;
+    graphemes.next()?;
+    Some(format!("{}…", text[..cut].trim_end()))
+}
+
+/// Issue title followed by its time to close (when requested and known) and any merged issues
+fn annotated_title(issue: &Issue, options: &OutputOptions) -> String {
+    let mut title = display_title(issue, options).into_owned();
+    if let Some(age) = issue.time_to_close().filter(|_| options.show_age) {
+        title.push_str(&format!(" (open {})", format_age(age)));
+    }
+    if options.show_comments {
+        let noun = if issue.comment_count == 1 { "comment" } else { "comments" };
+        title.push_str(&format!(" ({} {})", issue.comment_count, noun));
+    }
+    if !issue.merged.is_empty() {
+        let links: Vec<String> = issue.merged.iter().map(|m| merged_link(m, options.format)).collect();
+        title.push_str(&format!(" (also {})", links.join(", ")));
+    }
+    title
+}
+
+/// Reference to an issue merged into another, linked where the format supports it
+fn merged_link(merged: &MergedIssue, format: OutputFormat) -> String {
+    let reference = format!("{}#{}", merged.repository, merged.number);
     match format {
-        OutputFormat::Text => format_grouped_text(issues),
-        OutputFormat::Markdown => format_grouped_markdown(issues),
+        OutputFormat::Markdown | OutputFormat::Changelog | OutputFormat::GithubRelease => {
+            format!("[{}]({})", reference, merged.url)
+        }
+        OutputFormat::Org => format!("[[{}][{}]]", merged.url, reference),
+        OutputFormat::Text | OutputFormat::Urls | OutputFormat::Mermaid | OutputFormat::Table => reference,
+    }
+}
+
+/// Render a duration compactly in its largest whole unit, e.g. "3d", "5h" or "12m"
+pub fn format_age(age: Duration) -> String {
+    if age.num_days() > 0 {
+        format!("{}d", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{}h", age.num_hours())
+    } else {
+        format!("{}m", age.num_minutes().max(0))
+    }
+}
+
+/// Summarize median and max time to close, if any issue has both timestamps
+fn time_to_close_stats(issues: &[Issue]) -> Option<String> {
+    let mut ages: Vec<Duration> = issues.iter().filter_map(Issue::time_to_close).collect();
+    if ages.is_empty() {
+        return None;
+    }
+    ages.sort();
+
+    let mid = ages.len() / 2;
+    let median = if ages.len().is_multiple_of(2) {
+        (ages[mid - 1] + ages[mid]) / 2
+    } else {
+        ages[mid]
+    };
+    let max = ages[ages.len() - 1];
+
+    Some(format!(
+        "Time to close: median {}, max {}",
+        format_age(median),
+        format_age(max)
+    ))
+}
+
+/// Remove emoji graphemes and known `:shortcode:` forms, along with the space they leave behind
+pub fn strip_emoji(title: &str) -> String {
+    let mut output = String::with_capacity(title.len());
+    let mut rest = title;
+    let mut removed = false;
+
+    while let Some(grapheme) = rest.graphemes(true).next() {
+        let shortcode = grapheme
+            .starts_with(':')
+            .then(|| rest[1..].split_once(':'))
+            .flatten()
+            .map(|(name, _)| name)
+            .filter(|name| emojis::get_by_shortcode(name).is_some());
+
+        let skip = match shortcode {
+            Some(name) => name.len() + 2,
+            None if emojis::get(grapheme).is_some() => grapheme.len(),
+            None => {
+                // Drop the space an emoji left at the start or next to another space
+                let after_removed = removed && (output.is_empty() || output.ends_with(char::is_whitespace));
+                if !(after_removed && grapheme.chars().all(char::is_whitespace)) {
+                    output.push_str(grapheme);
+                    removed = false;
+                }
+                rest = &rest[grapheme.len()..];
+                continue;
+            }
+        };
+
+        rest = &rest[skip..];
+        removed = true;
+    }
+
+    if removed {
+        output.truncate(output.trim_end().len());
+    }
+    output
+}
+
+/// Format issues in one section per repository, each with its issue count
+pub fn format_by_repository(issues: &[Issue], options: &OutputOptions) -> String {
+    let sections: Vec<_> = group_by_repository(issues).into_iter().collect();
+    format_sections(issues, &sections, options)
+}
+
+/// Format issues in one section per milestone, keeping the incoming issue order
+pub fn format_by_milestone(issues: &[Issue], options: &OutputOptions) -> String {
+    format_sections(issues, &group_by_milestone(issues), options)
+}
+
+/// Format issues in one section per value of a custom field, keeping the incoming issue order
+pub fn format_by_field(issues: &[Issue], field: &str, options: &OutputOptions) -> String {
+    let grouped = group_by_key(issues, |issue| issue.fields.get(field).map(String::as_str), "Unspecified");
+    format_sections(issues, &grouped, options)
+}
+
+/// Render named sections of issues, each heading showing its issue count
+fn format_sections(issues: &[Issue], grouped: &[(&str, Vec<&Issue>)], options: &OutputOptions) -> String {
+    let style = styler(options);
+    let mut output = String::new();
+
+    match options.format {
+        OutputFormat::Text => {
+            push_text_header(&mut output, issues, options);
+            for (section, entries) in grouped {
+                output.push_str(&format!("▶ {} ({})\n", style.bold(section), count(entries.len(), options)));
+                for issue in entries {
+                    if options.compact {
+                        output.push_str(&format!("  {}\n", compact_line(issue, options)));
+                        continue;
+                    }
+                    output.push_str(&format!(
+                        "  • {} {}\n",
+                        style.link(&style.cyan(&format!("#{}", issue.number)), &issue.url),
+                        annotated_title(issue, options)
+                    ));
+                    if !options.hyperlinks {
+                        output.push_str(&format!("    {}\n", issue.url));
+                    }
+                }
+                output.push('\n');
+            }
+        }
+        OutputFormat::Markdown => {
+            push_markdown_header(&mut output, issues, options);
+            for (section, entries) in grouped {
+                let heading = format!("{} ({})", section, count(entries.len(), options));
+                push_markdown_section_start(&mut output, &heading, options.collapsible);
+                for issue in entries {
+                    output.push_str(&format!(
+                        "- [#{}]({}): {}\n",
+                        issue.number,
+                        issue.url,
+                        annotated_title(issue, options)
+                    ));
+                }
+                push_markdown_section_end(&mut output, options.collapsible);
+            }
+        }
+        OutputFormat::Org => {
+            push_org_header(&mut output, issues, options);
+            for (section, entries) in grouped {
+                output.push_str(&format!("** {} ({})\n", section, count(entries.len(), options)));
+                for issue in entries {
+                    push_org_issue(&mut output, issue, 3, true, options);
+                }
+            }
+        }
+        OutputFormat::Urls => {
+            for (_, entries) in grouped {
+                output.push_str(&format_list_urls(entries.iter().copied()));
+                output.push_str("\n\n");
+            }
+        }
+        // The changelog is already sectioned by label
+        OutputFormat::Changelog => return format_changelog(issues, options),
+        OutputFormat::GithubRelease => return format_github_release(issues, options),
+        // The diagram always shows parent relationships
+        OutputFormat::Mermaid => return format_mermaid(issues),
+        OutputFormat::Table => return format_table(issues, options),
     }
+
+    output.trim_end().to_string()
 }
 
-fn format_list_text(issues: &[Issue]) -> String {
+/// Heading inserted before each appended run, with the run time and the applied filters
+pub fn append_separator(now: DateTime<Local>, filters: &[String]) -> String {
+    let mut separator = format!("## {}", now.format("%Y-%m-%d %H:%M"));
+    if !filters.is_empty() {
+        separator.push_str(&format!(" ({})", filters.join(", ")));
+    }
+    separator
+}
+
+/// Write a report to a file, or append it below `separator` when given.
+/// Missing files are created in both cases.
+pub fn write_report(path: &Path, content: &str, separator: Option<&str>) -> Result<()> {
+    let Some(separator) = separator else {
+        return std::fs::write(path, format!("{}\n", content))
+            .with_context(|| format!("Failed to write output file {}", path.display()));
+    };
+
+    let existing = match std::fs::read_to_string(path) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read output file {}", path.display())),
+    };
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open output file {}", path.display()))?;
+
+    let gap = match existing.as_str() {
+        "" => "",
+        e if e.ends_with("\n\n") => "",
+        e if e.ends_with('\n') => "\n",
+        _ => "\n\n",
+    };
+    write!(file, "{}{}\n\n{}\n", gap, separator, content)
+        .with_context(|| format!("Failed to write output file {}", path.display()))
+}
+
+/// Append draft items, which have no number or URL, as a section of plain titles
+fn push_drafts_section(output: &mut String, drafts: &[Issue], options: &OutputOptions) {
+    let style = styler(options);
+    let (heading, bullet) = match options.format {
+        OutputFormat::Text | OutputFormat::Table => (format!("▶ {}", style.bold("Drafts")), "  •"),
+        OutputFormat::Markdown | OutputFormat::Changelog | OutputFormat::GithubRelease => {
+            ("### Drafts\n".to_string(), "-")
+        }
+        OutputFormat::Org => ("** Drafts".to_string(), "***"),
+        // Drafts have no URL to list
+        OutputFormat::Urls => return,
+        // Drafts have no relationships to draw
+        OutputFormat::Mermaid => return,
+    };
+
+    output.push_str(if options.format == OutputFormat::Org { "\n" } else { "\n\n" });
+    output.push_str(&heading);
+    output.push('\n');
+    for draft in drafts {
+        output.push_str(&format!("{} {}\n", bullet, display_title(draft, options)));
+    }
+    output.truncate(output.trim_end().len());
+}
+
+/// Write one file per parent issue (plus one for standalone issues) into `dir`,
+/// returning the paths written
+pub fn write_parent_files(dir: &Path, issues: &[Issue], options: &OutputOptions) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create output directory {}", dir.display()))?;
+
+    let grouped = group_by_parent(issues);
+    let mut parents: Vec<_> = grouped.with_parent.into_iter().collect();
+    parents.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut groups: Vec<(String, Vec<&Issue>)> =
+        parents.into_iter().map(|(title, (_, children))| (title, children)).collect();
+    if !grouped.orphans.is_empty() {
+        let standalone = ("Standalone".to_string(), grouped.orphans);
+        match options.standalone {
+            StandalonePlacement::First => groups.insert(0, standalone),
+            StandalonePlacement::Last => groups.push(standalone),
+            StandalonePlacement::Hide => {}
+        }
+    }
+
+    // Plain text is meant for the terminal; the files are documentation, so the default renders as markdown
+    let format = match options.format {
+        OutputFormat::Text => OutputFormat::Markdown,
+        format => format,
+    };
+    let extension = match format {
+        OutputFormat::Text => "txt",
+        OutputFormat::Markdown | OutputFormat::Changelog | OutputFormat::GithubRelease | OutputFormat::Mermaid => "md",
+        OutputFormat::Org => "org",
+        OutputFormat::Urls | OutputFormat::Table => "txt",
+    };
+
+    let flat = OutputOptions {
+        format,
+        group_by: GroupBy::None,
+        parent_only: false,
+        ..options.clone()
+    };
+
+    let mut used = HashSet::new();
+    let mut written = Vec::new();
+    for (title, children) in groups {
+        let base = slugify(&title);
+        let slug = (1..)
+            .map(|n| if n == 1 { base.clone() } else { format!("{}-{}", base, n) })
+            .find(|slug| used.insert(slug.clone()))
+            .expect("unbounded range");
+
+        let children: Vec<Issue> = children.into_iter().cloned().collect();
+        let heading = match format {
+            OutputFormat::Text | OutputFormat::Urls | OutputFormat::Table => {
+                format!("{}\n{}", title, "=".repeat(title.chars().count()))
+            }
+            OutputFormat::Markdown | OutputFormat::Changelog | OutputFormat::GithubRelease | OutputFormat::Mermaid => {
+                format!("# {}", title)
+            }
+            OutputFormat::Org => format!("#+TITLE: {}", title),
+        };
+        let content = format!("{}\n\n{}", heading, format_issues(&children, &flat));
+
+        let path = dir.join(format!("{}.{}", slug, extension));
+        write_report(&path, &content, None)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+/// Turn a title into a safe file name: lowercase ASCII letters and digits joined by hyphens
+pub fn slugify(title: &str) -> String {
+    let slug = title
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() { "untitled".to_string() } else { slug }
+}
+
+/// `Board: <link>` header line, or `Boards: <link>, <link>` when the report spans several boards
+fn boards_line(urls: &[String], link: impl Fn(&str) -> String) -> Option<String> {
+    let links: Vec<String> = urls.iter().map(|url| link(url)).collect();
+    match links.as_slice() {
+        [] => None,
+        [one] => Some(format!("Board: {}", one)),
+        _ => Some(format!("Boards: {}", links.join(", "))),
+    }
+}
+
+fn push_text_header(output: &mut String, issues: &[Issue], options: &OutputOptions) {
+    if options.no_header {
+        return;
+    }
+    output.push_str(&format!("Found {} issue(s):\n", count(issues.len(), options)));
+    if let Some(line) = boards_line(&options.project_urls, |url| url.to_string()) {
+        output.push_str(&format!("{}\n", line));
+    }
+    if options.show_stats
+        && let Some(line) = time_to_close_stats(issues)
+    {
+        output.push_str(&format!("{}\n", line));
+    }
+    output.push('\n');
+}
+
+fn push_markdown_header(output: &mut String, issues: &[Issue], options: &OutputOptions) {
+    if options.no_header {
+        return;
+    }
+    output.push_str(&format!("## Summary ({} issues)\n\n", count(issues.len(), options)));
+    if let Some(line) = boards_line(&options.project_urls, |url| format!("[{}]({})", url, url)) {
+        output.push_str(&format!("{}\n\n", line));
+    }
+    if options.show_stats
+        && let Some(line) = time_to_close_stats(issues)
+    {
+        output.push_str(&format!("{}\n\n", line));
+    }
+}
+
+fn format_list_text(issues: &[Issue], options: &OutputOptions) -> String {
+    let style = styler(options);
     let mut output = String::new();
 
-    output.push_str(&format!("Found {} issue(s):\n\n", issues.len()));
+    push_text_header(&mut output, issues, options);
 
     for issue in issues {
+        if options.compact {
+            output.push_str(&format!("{}\n", compact_line(issue, options)));
+            continue;
+        }
+
         output.push_str(&format!(
-            "• [{}#{}] {}\n",
-            issue.repository, issue.number, issue.title
+            "• {} {}\n",
+            style.link(&style.cyan(&format!("[{}#{}]", issue.repository, issue.number)), &issue.url),
+            annotated_title(issue, options)
         ));
-        output.push_str(&format!("  {}\n", issue.url));
+        if !options.hyperlinks {
+            output.push_str(&format!("  {}\n", issue.url));
+        }
 
         if let Some(parent) = &issue.parent {
             output.push_str(&format!("  Parent: {} ({})\n", parent.title, parent.url));
         }
 
         if let Some(closed_at) = issue.closed_at {
-            output.push_str(&format!("  Closed: {}\n", closed_at.format("%Y-%m-%d %H:%M")));
+            output.push_str(&format!("  Closed: {}\n", closed_at.format(&options.date_format)));
         }
 
         output.push('\n');
@@ -45,15 +867,35 @@ fn format_list_text(issues: &[Issue]) -> String {
     output.trim_end().to_string()
 }
 
-fn format_list_markdown(issues: &[Issue]) -> String {
+/// Styler for text output, honoring the color and hyperlink options
+fn styler(options: &OutputOptions) -> Styler {
+    Styler::new(options.color).with_hyperlinks(options.hyperlinks)
+}
+
+/// One-line text entry for --compact: `#123 title — repo (2024-10-15)`
+fn compact_line(issue: &Issue, options: &OutputOptions) -> String {
+    let style = styler(options);
+    let mut line = format!(
+        "{} {} — {}",
+        style.link(&style.cyan(&format!("#{}", issue.number)), &issue.url),
+        annotated_title(issue, options),
+        issue.repository
+    );
+    if let Some(closed_at) = issue.closed_at {
+        line.push_str(&format!(" ({})", closed_at.format(date_only_format(&options.date_format))));
+    }
+    line
+}
+
+fn format_list_markdown(issues: &[Issue], options: &OutputOptions) -> String {
     let mut output = String::new();
 
-    output.push_str(&format!("## Summary ({} issues)\n\n", issues.len()));
+    push_markdown_header(&mut output, issues, options);
 
     for issue in issues {
         output.push_str(&format!(
             "- **[{}#{}]({})**: {}\n",
-            issue.repository, issue.number, issue.url, issue.title
+            issue.repository, issue.number, issue.url, annotated_title(issue, options)
         ));
 
         if let Some(parent) = &issue.parent {
@@ -63,7 +905,7 @@ fn format_list_markdown(issues: &[Issue]) -> String {
         if let Some(closed_at) = issue.closed_at {
             output.push_str(&format!(
                 "  - Closed: {}\n",
-                closed_at.format("%Y-%m-%d %H:%M")
+                closed_at.format(&options.date_format)
             ));
         }
     }
@@ -71,81 +913,479 @@ fn format_list_markdown(issues: &[Issue]) -> String {
     output.trim_end().to_string()
 }
 
-fn format_grouped_text(issues: &[Issue]) -> String {
+fn format_grouped_text(issues: &[Issue], options: &OutputOptions) -> String {
+    let style = styler(options);
     let grouped = group_by_parent(issues);
     let mut output = String::new();
 
-    output.push_str(&format!("Found {} issue(s):\n\n", issues.len()));
+    push_text_header(&mut output, issues, options);
 
-    // First, output issues with parents
+    // Issues with parents
+    let mut parents = String::new();
     for (parent_title, (parent_info, children)) in grouped.with_parent.iter() {
-        output.push_str(&format!("▶ {}\n", parent_title));
+        parents.push_str(&format!("▶ {}\n", style.bold(parent_title)));
         if let Some(info) = parent_info {
-            output.push_str(&format!("  {}\n", info.url));
+            parents.push_str(&format!("  {}\n", info.url));
         }
-        output.push_str("  Completed:\n");
+        parents.push_str("  Completed:\n");
 
         for issue in children {
-            output.push_str(&format!(
-                "    • [{}#{}] {}\n",
-                issue.repository, issue.number, issue.title
+            if options.compact {
+                parents.push_str(&format!("    {}\n", compact_line(issue, options)));
+                continue;
+            }
+            parents.push_str(&format!(
+                "    • {} {}\n",
+                style.link(&style.cyan(&format!("[{}#{}]", issue.repository, issue.number)), &issue.url),
+                annotated_title(issue, options)
             ));
         }
-        output.push('\n');
+        parents.push('\n');
     }
 
-    // Then, output orphan issues (no parent)
+    // Orphan issues (no parent)
+    let mut standalone = String::new();
     if !grouped.orphans.is_empty() {
-        output.push_str("▶ Standalone Issues\n");
+        standalone.push_str(&format!("▶ {}\n", style.bold("Standalone Issues")));
         for issue in &grouped.orphans {
-            output.push_str(&format!(
-                "  • [{}#{}] {}\n",
-                issue.repository, issue.number, issue.title
+            if options.compact {
+                standalone.push_str(&format!("  {}\n", compact_line(issue, options)));
+                continue;
+            }
+            standalone.push_str(&format!(
+                "  • {} {}\n",
+                style.link(&style.cyan(&format!("[{}#{}]", issue.repository, issue.number)), &issue.url),
+                annotated_title(issue, options)
             ));
-            output.push_str(&format!("    {}\n", issue.url));
+            if !options.hyperlinks {
+                standalone.push_str(&format!("    {}\n", issue.url));
+            }
         }
+        standalone.push('\n');
     }
 
+    output.push_str(&arrange_standalone(parents, standalone, options.standalone));
     output.trim_end().to_string()
 }
 
-fn format_grouped_markdown(issues: &[Issue]) -> String {
+fn format_grouped_markdown(issues: &[Issue], options: &OutputOptions) -> String {
     let grouped = group_by_parent(issues);
     let mut output = String::new();
 
-    output.push_str(&format!("## Summary ({} issues)\n\n", issues.len()));
+    push_markdown_header(&mut output, issues, options);
 
-    // First, output issues with parents
+    // Issues with parents
+    let mut parents = String::new();
     for (parent_title, (parent_info, children)) in grouped.with_parent.iter() {
-        if let Some(info) = parent_info {
-            output.push_str(&format!("### [{}]({})\n\n", parent_title, info.url));
-        } else {
-            output.push_str(&format!("### {}\n\n", parent_title));
-        }
+        let heading = match parent_info {
+            Some(info) if options.collapsible => {
+                format!("<a href=\"{}\">{}</a>", info.url, parent_title)
+            }
+            Some(info) => format!("[{}]({})", parent_title, info.url),
+            None => parent_title.clone(),
+        };
+        push_markdown_section_start(&mut parents, &heading, options.collapsible);
 
         for issue in children {
-            output.push_str(&format!(
+            parents.push_str(&format!(
                 "- [{}#{}]({}): {}\n",
-                issue.repository, issue.number, issue.url, issue.title
+                issue.repository, issue.number, issue.url, annotated_title(issue, options)
             ));
         }
-        output.push('\n');
+        push_markdown_section_end(&mut parents, options.collapsible);
     }
 
-    // Then, output orphan issues (no parent)
+    // Orphan issues (no parent)
+    let mut standalone = String::new();
     if !grouped.orphans.is_empty() {
-        output.push_str("### Standalone Issues\n\n");
+        push_markdown_section_start(&mut standalone, "Standalone Issues", options.collapsible);
         for issue in &grouped.orphans {
-            output.push_str(&format!(
+            standalone.push_str(&format!(
                 "- [{}#{}]({}): {}\n",
-                issue.repository, issue.number, issue.url, issue.title
+                issue.repository, issue.number, issue.url, annotated_title(issue, options)
             ));
         }
+        push_markdown_section_end(&mut standalone, options.collapsible);
+    }
+
+    output.push_str(&arrange_standalone(parents, standalone, options.standalone));
+    output.trim_end().to_string()
+}
+
+fn push_org_header(output: &mut String, issues: &[Issue], options: &OutputOptions) {
+    if options.no_header {
+        return;
+    }
+    output.push_str(&format!("* Summary ({} issues)\n", count(issues.len(), options)));
+    if let Some(line) = boards_line(&options.project_urls, |url| format!("[[{}]]", url)) {
+        output.push_str(&format!("{}\n", line));
+    }
+    if options.show_stats
+        && let Some(line) = time_to_close_stats(issues)
+    {
+        output.push_str(&format!("{}\n", line));
+    }
+    output.push('\n');
+}
+
+/// Push an Org heading for an issue, with a property drawer when there is anything to record
+fn push_org_issue(output: &mut String, issue: &Issue, level: usize, with_parent: bool, options: &OutputOptions) {
+    output.push_str(&format!(
+        "{} [[{}][{}#{}]] {}\n",
+        "*".repeat(level),
+        issue.url,
+        issue.repository,
+        issue.number,
+        annotated_title(issue, options)
+    ));
+
+    let parent = issue.parent.as_ref().filter(|_| with_parent);
+    if parent.is_none() && issue.closed_at.is_none() {
+        return;
+    }
+
+    output.push_str(":PROPERTIES:\n");
+    if let Some(closed_at) = issue.closed_at {
+        output.push_str(&format!(":CLOSED: [{}]\n", closed_at.format("%Y-%m-%d %a %H:%M")));
+    }
+    if let Some(parent) = parent {
+        output.push_str(&format!(":PARENT: [[{}][{}]]\n", parent.url, parent.title));
+    }
+    output.push_str(":END:\n");
+}
+
+fn format_list_org(issues: &[Issue], options: &OutputOptions) -> String {
+    let mut output = String::new();
+
+    push_org_header(&mut output, issues, options);
+
+    for issue in issues {
+        push_org_issue(&mut output, issue, 2, true, options);
+    }
+
+    output.trim_end().to_string()
+}
+
+fn format_grouped_org(issues: &[Issue], options: &OutputOptions) -> String {
+    let grouped = group_by_parent(issues);
+    let mut output = String::new();
+
+    push_org_header(&mut output, issues, options);
+
+    // Issues with parents
+    let mut parents = String::new();
+    for (parent_title, (parent_info, children)) in grouped.with_parent.iter() {
+        match parent_info {
+            Some(info) if !info.url.is_empty() => {
+                parents.push_str(&format!("** [[{}][{}]]\n", info.url, parent_title))
+            }
+            _ => parents.push_str(&format!("** {}\n", parent_title)),
+        }
+        for issue in children {
+            push_org_issue(&mut parents, issue, 3, false, options);
+        }
+    }
+
+    // Orphan issues (no parent)
+    let mut standalone = String::new();
+    if !grouped.orphans.is_empty() {
+        standalone.push_str("** Standalone Issues\n");
+        for issue in &grouped.orphans {
+            push_org_issue(&mut standalone, issue, 3, false, options);
+        }
+    }
+
+    output.push_str(&arrange_standalone(parents, standalone, options.standalone));
+    output.trim_end().to_string()
+}
+
+/// List issue URLs, one per line
+fn format_list_urls<'a>(issues: impl IntoIterator<Item = &'a Issue>) -> String {
+    issues
+        .into_iter()
+        .flat_map(|issue| std::iter::once(issue.url.as_str()).chain(issue.merged.iter().map(|m| m.url.as_str())))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// URLs of the parents that have one
+fn parent_urls<'a>(grouped: &'a GroupedIssues) -> Vec<&'a str> {
+    let mut parents: Vec<_> = grouped.with_parent.iter().collect();
+    parents.sort_by(|a, b| a.0.cmp(b.0));
+    parents
+        .into_iter()
+        .filter_map(|(_, (info, _))| info.as_ref().map(|info| info.url.as_str()))
+        .filter(|url| !url.is_empty())
+        .collect()
+}
+
+/// List each parent URL followed by its children's URLs, with standalone issues where `placement` puts them
+fn format_grouped_urls(issues: &[Issue], placement: StandalonePlacement) -> String {
+    let grouped = group_by_parent(issues);
+    let mut parents: Vec<_> = grouped.with_parent.iter().collect();
+    parents.sort_by(|a, b| a.0.cmp(b.0));
+    let mut blocks = Vec::new();
+
+    for (_, (parent_info, children)) in parents {
+        let mut block = String::new();
+        if let Some(info) = parent_info.as_ref().filter(|info| !info.url.is_empty()) {
+            block.push_str(&info.url);
+            block.push('\n');
+        }
+        block.push_str(&format_list_urls(children.iter().copied()));
+        blocks.push(block);
+    }
+
+    if !grouped.orphans.is_empty() {
+        let standalone = format_list_urls(grouped.orphans.iter().copied());
+        match placement {
+            StandalonePlacement::First => blocks.insert(0, standalone),
+            StandalonePlacement::Last => blocks.push(standalone),
+            StandalonePlacement::Hide => {}
+        }
+    }
+
+    blocks.join("\n\n")
+}
+
+/// Start a grouped markdown section, either as a heading or a collapsible `<details>` block
+fn push_markdown_section_start(output: &mut String, heading: &str, collapsible: bool) {
+    if collapsible {
+        output.push_str(&format!("<details>\n<summary>{}</summary>\n\n", heading));
+    } else {
+        output.push_str(&format!("### {}\n\n", heading));
+    }
+}
+
+fn push_markdown_section_end(output: &mut String, collapsible: bool) {
+    if collapsible {
+        output.push_str("\n</details>\n");
+    }
+    output.push('\n');
+}
+
+fn format_changelog(issues: &[Issue], options: &OutputOptions) -> String {
+    // Preserve section order as first seen in the mapping
+    let mut sections: Vec<(&str, Vec<&Issue>)> = Vec::new();
+    for (_, section) in &options.changelog_sections {
+        if !sections.iter().any(|(name, _)| name == section) {
+            sections.push((section, Vec::new()));
+        }
+    }
+    let mut other = Vec::new();
+
+    for issue in issues {
+        let section = options.changelog_sections.iter().find_map(|(label, section)| {
+            issue
+                .labels
+                .iter()
+                .any(|l| l.eq_ignore_ascii_case(label))
+                .then_some(section.as_str())
+        });
+
+        match section.and_then(|name| sections.iter_mut().find(|(s, _)| *s == name)) {
+            Some((_, entries)) => entries.push(issue),
+            None => other.push(issue),
+        }
+    }
+
+    let mut output = String::from("## [Unreleased]\n\n");
+
+    for (section, entries) in sections.iter().chain(std::iter::once(&("Other", other))) {
+        if entries.is_empty() {
+            continue;
+        }
+        output.push_str(&format!("### {}\n\n", section));
+        for issue in entries {
+            output.push_str(&format!("- {} (#{})\n", display_title(issue, options), issue.number));
+        }
+        output.push('\n');
+    }
+
+    output.trim_end().to_string()
+}
+
+/// Title column width for --format table when --max-title-width isn't given
+const TABLE_TITLE_WIDTH: usize = 60;
+
+/// Parent titles are cut shorter; the column only hints at the grouping
+const TABLE_PARENT_WIDTH: usize = 30;
+
+/// Aligned Repo, #, Title, Closed, and Parent columns. Widths are counted in graphemes, so the
+/// layout is the same in a terminal and when piped (double-width characters may shift a row)
+fn format_table(issues: &[Issue], options: &OutputOptions) -> String {
+    let style = styler(options);
+    let cell = |text: &str, width: usize| truncate_graphemes(text, width).unwrap_or_else(|| text.to_string());
+    let title_width = options.max_title_width.unwrap_or(TABLE_TITLE_WIDTH);
+
+    let rows: Vec<[String; 5]> = issues
+        .iter()
+        .map(|issue| {
+            [
+                issue.repository.clone(),
+                format!("#{}", issue.number),
+                cell(&display_title(issue, options), title_width),
+                issue
+                    .closed_at
+                    .map_or_else(|| "-".to_string(), |closed_at| closed_at.format(date_only_format(&options.date_format)).to_string()),
+                issue
+                    .parent
+                    .as_ref()
+                    .map_or_else(|| "-".to_string(), |parent| cell(&parent.title, TABLE_PARENT_WIDTH)),
+            ]
+        })
+        .collect();
+
+    let headers = ["Repo", "#", "Title", "Closed", "Parent"];
+    let len = |text: &str| text.graphemes(true).count();
+    let mut widths = headers.map(len);
+    for row in &rows {
+        for (width, text) in widths.iter_mut().zip(row) {
+            *width = (*width).max(len(text));
+        }
+    }
+    let pad = |text: &str, width: usize| " ".repeat(width - len(text));
+
+    let mut output = String::new();
+    push_text_header(&mut output, issues, options);
+
+    let header: Vec<String> = headers
+        .iter()
+        .zip(widths)
+        .map(|(header, width)| format!("{}{}", style.bold(header), pad(header, width)))
+        .collect();
+    output.push_str(header.join("  ").trim_end());
+    output.push('\n');
+    let rule: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+    output.push_str(&rule.join("  "));
+    output.push('\n');
+
+    for (issue, row) in issues.iter().zip(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (text, width))| {
+                let shown = if i == 1 { style.link(&style.cyan(text), &issue.url) } else { text.clone() };
+                format!("{}{}", shown, pad(text, width))
+            })
+            .collect();
+        output.push_str(cells.join("  ").trim_end());
+        output.push('\n');
+    }
+
+    output.trim_end().to_string()
+}
+
+/// Format issues as a fenced Mermaid flowchart: each parent links to its children, and
+/// standalone issues are unconnected nodes. Parents are sorted by title for stable output.
+fn format_mermaid(issues: &[Issue]) -> String {
+    let grouped = group_by_parent(issues);
+    let mut parents: Vec<_> = grouped.with_parent.iter().collect();
+    parents.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut output = String::from("```mermaid\nflowchart LR\n");
+    for (i, (title, (_, children))) in parents.into_iter().enumerate() {
+        let parent_id = format!("parent_{}", i + 1);
+        output.push_str(&format!("    {}[\"{}\"]\n", parent_id, mermaid_label(title)));
+        for issue in children {
+            output.push_str(&format!("    {} --> {}\n", parent_id, mermaid_node(issue)));
+        }
+    }
+    for issue in &grouped.orphans {
+        output.push_str(&format!("    {}\n", mermaid_node(issue)));
+    }
+    output.push_str("```");
+    output
+}
+
+/// Node declaration for an issue, with an id built from its repository and number
+fn mermaid_node(issue: &Issue) -> String {
+    let id: String = format!("{}_{}", issue.repository, issue.number)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let label = format!("{}#{} {}", issue.repository, issue.number, issue.title);
+    format!("issue_{}[\"{}\"]", id, mermaid_label(&label))
+}
+
+/// Escape text for a quoted Mermaid label, where a double quote would end the label early
+fn mermaid_label(text: &str) -> String {
+    text.replace('"', "#quot;").replace(['\n', '\r'], " ")
+}
+
+/// Format issues as a GitHub Release body, crediting assignees like GitHub's generated notes
+fn format_github_release(issues: &[Issue], options: &OutputOptions) -> String {
+    let mut output = String::from("## What's Changed\n\n");
+    let mut contributors: Vec<&str> = Vec::new();
+
+    for issue in issues {
+        output.push_str(&format!("* {}", display_title(issue, options)));
+        if !issue.assignees.is_empty() {
+            let logins: Vec<String> = issue.assignees.iter().map(|login| format!("@{}", login)).collect();
+            output.push_str(&format!(" by {}", logins.join(", ")));
+        }
+        output.push_str(&format!(" in {}\n", issue.url));
+
+        for login in &issue.assignees {
+            if !contributors.contains(&login.as_str()) {
+                contributors.push(login);
+            }
+        }
+    }
+
+    if !contributors.is_empty() {
+        contributors.sort_by_key(|login| login.to_lowercase());
+        let mentions: Vec<String> = contributors.iter().map(|login| format!("@{}", login)).collect();
+        output.push_str(&format!("\n## Contributors\n\n{}\n", mentions.join(", ")));
     }
 
     output.trim_end().to_string()
 }
 
+/// Group issues by repository, with repositories sorted by name and issues by number
+fn group_by_repository(issues: &[Issue]) -> BTreeMap<&str, Vec<&Issue>> {
+    let mut grouped: BTreeMap<&str, Vec<&Issue>> = BTreeMap::new();
+    for issue in issues {
+        grouped.entry(issue.repository.as_str()).or_default().push(issue);
+    }
+    for entries in grouped.values_mut() {
+        entries.sort_by_key(|issue| issue.number);
+    }
+    grouped
+}
+
+/// Group issues by milestone in first-seen order, with unscheduled issues last
+fn group_by_milestone(issues: &[Issue]) -> Vec<(&str, Vec<&Issue>)> {
+    group_by_key(issues, |issue| issue.milestone.as_deref(), "No Milestone")
+}
+
+/// Group issues by a key in first-seen order; issues without a key go last under `missing`
+fn group_by_key<'a>(
+    issues: &'a [Issue],
+    key: impl Fn(&'a Issue) -> Option<&'a str>,
+    missing: &'static str,
+) -> Vec<(&'a str, Vec<&'a Issue>)> {
+    let mut grouped: Vec<(&str, Vec<&Issue>)> = Vec::new();
+    let mut unkeyed = Vec::new();
+
+    for issue in issues {
+        let Some(value) = key(issue) else {
+            unkeyed.push(issue);
+            continue;
+        };
+        match grouped.iter_mut().find(|(title, _)| *title == value) {
+            Some((_, entries)) => entries.push(issue),
+            None => grouped.push((value, vec![issue])),
+        }
+    }
+
+    if !unkeyed.is_empty() {
+        grouped.push((missing, unkeyed));
+    }
+    grouped
+}
+
 struct GroupedIssues<'a> {
     with_parent: HashMap<String, (Option<ParentInfo>, Vec<&'a Issue>)>,
     orphans: Vec<&'a Issue>,
@@ -182,3 +1422,943 @@ fn group_by_parent(issues: &[Issue]) -> GroupedIssues<'_> {
         orphans,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ParentIssue;
+    use chrono::{Local, TimeZone, Utc};
+
+    fn sample_issues() -> Vec<Issue> {
+        vec![
+            Issue {
+                number: 42,
+                title: "Fix login button alignment".to_string(),
+                url: "https://github.com/myorg/repo/issues/42".to_string(),
+                closed_at: Some(Utc.with_ymd_and_hms(2024, 1, 15, 14, 30, 0).unwrap()),
+                parent: None,
+                repository: "myorg/repo".to_string(),
+                ..Default::default()
+            },
+            Issue {
+                number: 45,
+                title: "Add dark mode support".to_string(),
+                url: "https://github.com/myorg/repo/issues/45".to_string(),
+                closed_at: Some(Utc.with_ymd_and_hms(2024, 1, 15, 16, 0, 0).unwrap()),
+                parent: Some(ParentIssue {
+                    number: 40,
+                    title: "UI Improvements".to_string(),
+                    url: "https://github.com/myorg/repo/issues/40".to_string(),
+                }),
+                repository: "myorg/repo".to_string(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn options(format: OutputFormat, group_by: GroupBy) -> OutputOptions {
+        OutputOptions {
+            format,
+            group_by,
+            ..OutputOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_list_text_default_options() {
+        let output = format_issues(&sample_issues(), &options(OutputFormat::Text, GroupBy::None));
+        let expected = "Found 2 issue(s):\n\n\
+            • [myorg/repo#42] Fix login button alignment\n  \
+            https://github.com/myorg/repo/issues/42\n  \
+            Closed: 2024-01-15 14:30\n\n\
+            • [myorg/repo#45] Add dark mode support\n  \
+            https://github.com/myorg/repo/issues/45\n  \
+            Parent: UI Improvements (https://github.com/myorg/repo/issues/40)\n  \
+            Closed: 2024-01-15 16:00";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_list_markdown_default_options() {
+        let output = format_issues(&sample_issues(), &options(OutputFormat::Markdown, GroupBy::None));
+        let expected = "## Summary (2 issues)\n\n\
+            - **[myorg/repo#42](https://github.com/myorg/repo/issues/42)**: Fix login button alignment\n  \
+            - Closed: 2024-01-15 14:30\n\
+            - **[myorg/repo#45](https://github.com/myorg/repo/issues/45)**: Add dark mode support\n  \
+            - Parent: [UI Improvements](https://github.com/myorg/repo/issues/40)\n  \
+            - Closed: 2024-01-15 16:00";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_grouped_text_default_options() {
+        let output = format_issues(&sample_issues(), &options(OutputFormat::Text, GroupBy::Parent));
+        let expected = "Found 2 issue(s):\n\n\
+            ▶ UI Improvements\n  \
+            https://github.com/myorg/repo/issues/40\n  \
+            Completed:\n    \
+            • [myorg/repo#45] Add dark mode support\n\n\
+            ▶ Standalone Issues\n  \
+            • [myorg/repo#42] Fix login button alignment\n    \
+            https://github.com/myorg/repo/issues/42";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_grouped_markdown_default_options() {
+        let output = format_issues(&sample_issues(), &options(OutputFormat::Markdown, GroupBy::Parent));
+        let expected = "## Summary (2 issues)\n\n\
+            ### [UI Improvements](https://github.com/myorg/repo/issues/40)\n\n\
+            - [myorg/repo#45](https://github.com/myorg/repo/issues/45): Add dark mode support\n\n\
+            ### Standalone Issues\n\n\
+            - [myorg/repo#42](https://github.com/myorg/repo/issues/42): Fix login button alignment";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_changelog_sections() {
+        let mut issues = sample_issues();
+        issues[0].labels = vec!["bug".to_string()];
+        issues[1].labels = vec!["Feat".to_string(), "ui".to_string()];
+        issues.push(Issue {
+            number: 50,
+            title: "Misc cleanup".to_string(),
+            ..Default::default()
+        });
+
+        let output = format_issues(&issues, &options(OutputFormat::Changelog, GroupBy::None));
+        let expected = "## [Unreleased]\n\n\
+            ### Features\n\n\
+            - Add dark mode support (#45)\n\n\
+            ### Bug Fixes\n\n\
+            - Fix login button alignment (#42)\n\n\
+            ### Other\n\n\
+            - Misc cleanup (#50)";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_github_release_notes() {
+        let mut issues = sample_issues();
+        issues[0].assignees = vec!["bob".to_string(), "Alice".to_string()];
+        issues[1].assignees = vec!["bob".to_string()];
+        issues.push(Issue {
+            number: 50,
+            title: "Misc cleanup".to_string(),
+            url: "https://github.com/myorg/repo/issues/50".to_string(),
+            ..Default::default()
+        });
+
+        let output = format_issues(&issues, &options(OutputFormat::GithubRelease, GroupBy::Parent));
+        let expected = "## What's Changed\n\n\
+            * Fix login button alignment by @bob, @Alice in https://github.com/myorg/repo/issues/42\n\
+            * Add dark mode support by @bob in https://github.com/myorg/repo/issues/45\n\
+            * Misc cleanup in https://github.com/myorg/repo/issues/50\n\n\
+            ## Contributors\n\n\
+            @Alice, @bob";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_mermaid_diagram() {
+        let mut issues = sample_issues();
+        issues[0].title = "Fix \"login\" button".to_string();
+        issues.push(Issue {
+            number: 46,
+            title: "Update color palette".to_string(),
+            repository: "my-org/web.app".to_string(),
+            parent: issues[1].parent.clone(),
+            ..Default::default()
+        });
+
+        let output = format_issues(&issues, &options(OutputFormat::Mermaid, GroupBy::None));
+        let expected = "```mermaid\n\
+            flowchart LR\n    \
+            parent_1[\"UI Improvements\"]\n    \
+            parent_1 --> issue_myorg_repo_45[\"myorg/repo#45 Add dark mode support\"]\n    \
+            parent_1 --> issue_my_org_web_app_46[\"my-org/web.app#46 Update color palette\"]\n    \
+            issue_myorg_repo_42[\"myorg/repo#42 Fix #quot;login#quot; button\"]\n\
+            ```";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_parse_changelog_sections() {
+        let sections = parse_changelog_sections("Feat = New Stuff, docs=Documentation").unwrap();
+        assert_eq!(
+            sections,
+            vec![
+                ("feat".to_string(), "New Stuff".to_string()),
+                ("docs".to_string(), "Documentation".to_string()),
+            ]
+        );
+        assert!(parse_changelog_sections("nonsense").is_err());
+    }
+
+    #[test]
+    fn test_compact_text() {
+        let compact = OutputOptions {
+            compact: true,
+            no_header: true,
+            ..OutputOptions::default()
+        };
+        assert_eq!(
+            format_issues(&sample_issues(), &compact),
+            "#42 Fix login button alignment — myorg/repo (2024-01-15)\n\
+             #45 Add dark mode support — myorg/repo (2024-01-15)"
+        );
+
+        let grouped = format_issues(&sample_issues(), &OutputOptions { group_by: GroupBy::Parent, ..compact });
+        assert!(grouped.contains("  Completed:\n    #45 Add dark mode support — myorg/repo (2024-01-15)\n"));
+        assert!(grouped.ends_with("▶ Standalone Issues\n  #42 Fix login button alignment — myorg/repo (2024-01-15)"));
+    }
+
+    #[test]
+    fn test_standalone_placement() {
+        let options = |format, standalone| OutputOptions {
+            format,
+            group_by: GroupBy::Parent,
+            standalone,
+            compact: true,
+            no_header: true,
+            ..OutputOptions::default()
+        };
+
+        let first = format_issues(&sample_issues(), &options(OutputFormat::Text, StandalonePlacement::First));
+        assert!(first.starts_with("▶ Standalone Issues\n  #42 Fix login button alignment — myorg/repo (2024-01-15)\n\n▶ "));
+        let hidden = format_issues(&sample_issues(), &options(OutputFormat::Text, StandalonePlacement::Hide));
+        assert!(!hidden.contains("Standalone") && !hidden.contains("#42"), "{}", hidden);
+
+        let markdown = format_issues(&sample_issues(), &options(OutputFormat::Markdown, StandalonePlacement::First));
+        assert!(markdown.starts_with("### Standalone Issues\n\n- [myorg/repo#42]"), "{}", markdown);
+        let org = format_issues(&sample_issues(), &options(OutputFormat::Org, StandalonePlacement::Hide));
+        assert!(!org.contains("Standalone"));
+
+        let urls = format_issues(&sample_issues(), &options(OutputFormat::Urls, StandalonePlacement::First));
+        assert!(urls.starts_with("https://github.com/myorg/repo/issues/42\n\n"), "{}", urls);
+
+        let summary = format_parent_summary(&sample_issues(), &options(OutputFormat::Text, StandalonePlacement::Hide));
+        assert!(!summary.contains("standalone"));
+    }
+
+    #[test]
+    fn test_repos_summary() {
+        let mut issues = sample_issues();
+        issues.push(Issue {
+            number: 7,
+            title: "Rate limit retries".to_string(),
+            repository: "myorg/api".to_string(),
+            ..Default::default()
+        });
+        issues.push(Issue {
+            title: "Idea".to_string(),
+            is_draft: true,
+            ..Default::default()
+        });
+        assert_eq!(repository_counts(&issues), vec![("myorg/repo", 2), ("myorg/api", 1)]);
+
+        let opts = OutputOptions {
+            repos_summary: true,
+            ..OutputOptions::default()
+        };
+        assert!(format_issues(&issues, &opts).ends_with("\n\nRepos: myorg/repo (2), myorg/api (1)"));
+
+        let markdown = OutputOptions {
+            format: OutputFormat::Markdown,
+            ..opts.clone()
+        };
+        assert!(format_issues(&issues, &markdown).ends_with("\n\n**Repos:** myorg/repo (2), myorg/api (1)"));
+
+        let org = OutputOptions {
+            format: OutputFormat::Org,
+            ..opts
+        };
+        assert!(!format_issues(&issues, &org).contains("Repos:"));
+    }
+
+    #[test]
+    fn test_text_hyperlinks() {
+        let opts = OutputOptions {
+            hyperlinks: true,
+            no_header: true,
+            ..OutputOptions::default()
+        };
+        let output = format_issues(&sample_issues(), &opts);
+        assert!(output.starts_with(
+            "• \x1b]8;;https://github.com/myorg/repo/issues/42\x1b\\[myorg/repo#42]\x1b]8;;\x1b\\ Fix login button alignment\n"
+        ));
+        // The link replaces the URL line
+        assert!(!output.contains("  https://github.com/myorg/repo/issues/42\n"));
+    }
+
+    #[test]
+    fn test_grouped_text_color() {
+        let opts = OutputOptions {
+            group_by: GroupBy::Parent,
+            color: true,
+            ..OutputOptions::default()
+        };
+        let output = format_issues(&sample_issues(), &opts);
+        assert!(output.contains("▶ \x1b[1mUI Improvements\x1b[0m"));
+        assert!(output.contains("\x1b[36m[myorg/repo#45]\x1b[0m Add dark mode support"));
+    }
+
+    #[test]
+    fn test_grouped_markdown_collapsible() {
+        let opts = OutputOptions {
+            format: OutputFormat::Markdown,
+            group_by: GroupBy::Parent,
+            collapsible: true,
+            ..OutputOptions::default()
+        };
+        let output = format_issues(&sample_issues(), &opts);
+        let expected = "## Summary (2 issues)\n\n\
+            <details>\n\
+            <summary><a href=\"https://github.com/myorg/repo/issues/40\">UI Improvements</a></summary>\n\n\
+            - [myorg/repo#45](https://github.com/myorg/repo/issues/45): Add dark mode support\n\n\
+            </details>\n\n\
+            <details>\n\
+            <summary>Standalone Issues</summary>\n\n\
+            - [myorg/repo#42](https://github.com/myorg/repo/issues/42): Fix login button alignment\n\n\
+            </details>";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_project_url_header() {
+        let mut opts = options(OutputFormat::Text, GroupBy::None);
+        opts.project_urls = vec!["https://github.com/orgs/myorg/projects/5".to_string()];
+        let output = format_issues(&sample_issues(), &opts);
+        assert!(output.starts_with("Found 2 issue(s):\nBoard: https://github.com/orgs/myorg/projects/5\n\n•"));
+
+        opts.format = OutputFormat::Markdown;
+        let output = format_issues(&sample_issues(), &opts);
+        assert!(output.starts_with(
+            "## Summary (2 issues)\n\nBoard: [https://github.com/orgs/myorg/projects/5](https://github.com/orgs/myorg/projects/5)\n\n-"
+        ));
+    }
+
+    #[test]
+    fn test_multiple_boards_header() {
+        let mut opts = options(OutputFormat::Text, GroupBy::None);
+        opts.project_urls = vec![
+            "https://github.com/orgs/myorg/projects/5".to_string(),
+            "https://github.com/orgs/myorg/projects/7".to_string(),
+        ];
+        let output = format_issues(&sample_issues(), &opts);
+        assert!(output.starts_with(
+            "Found 2 issue(s):\nBoards: https://github.com/orgs/myorg/projects/5, https://github.com/orgs/myorg/projects/7\n\n"
+        ));
+    }
+
+    #[test]
+    fn test_parent_summary() {
+        let mut issues = sample_issues();
+        issues.push(Issue {
+            number: 46,
+            title: "Polish settings page".to_string(),
+            parent: Some(ParentIssue {
+                number: 41,
+                title: "Settings".to_string(),
+                url: String::new(),
+            }),
+            ..Default::default()
+        });
+        issues.push(Issue {
+            number: 47,
+            title: "Theme toggle".to_string(),
+            parent: issues[1].parent.clone(),
+            ..Default::default()
+        });
+
+        let mut opts = OutputOptions {
+            parent_only: true,
+            ..OutputOptions::default()
+        };
+        let output = format_issues(&issues, &opts);
+        let expected = "Found 4 issue(s):\n\n\
+            • Settings (1 completed)\n\
+            • UI Improvements (2 completed)\n\
+            • 1 standalone issues";
+        assert_eq!(output, expected);
+
+        opts.format = OutputFormat::Markdown;
+        let output = format_issues(&issues, &opts);
+        let expected = "## Summary (4 issues)\n\n\
+            - Settings (1 completed)\n\
+            - [UI Improvements](https://github.com/myorg/repo/issues/40) (2 completed)\n\
+            - 1 standalone issues";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_strip_emoji() {
+        assert_eq!(strip_emoji("🐛 Fix crash"), "Fix crash");
+        assert_eq!(strip_emoji("Ship it 🚀 today"), "Ship it today");
+        assert_eq!(strip_emoji(":bug: Fix crash :sparkles:"), "Fix crash");
+        assert_eq!(strip_emoji("👩‍💻 Pairing ✨"), "Pairing");
+        // Non-emoji text, including unknown shortcodes and times, is untouched
+        assert_eq!(strip_emoji("Café at 12:30:00 #42"), "Café at 12:30:00 #42");
+        assert_eq!(strip_emoji("Use :notashortcode: here"), "Use :notashortcode: here");
+    }
+
+    #[test]
+    fn test_strip_emoji_option() {
+        let mut issues = sample_issues();
+        issues[0].title = "🐛 Fix login button alignment".to_string();
+        let opts = OutputOptions {
+            strip_emoji: true,
+            ..OutputOptions::default()
+        };
+        let output = format_issues(&issues, &opts);
+        assert!(output.contains("[myorg/repo#42] Fix login button alignment\n"));
+        // The fetched data itself is left alone
+        assert!(issues[0].title.starts_with('🐛'));
+    }
+
+    #[test]
+    fn test_sort_by_parent() {
+        let child = |number: u64, parent: Option<&str>| Issue {
+            number,
+            parent: parent.map(|title| ParentIssue {
+                number: 1,
+                title: title.to_string(),
+                url: format!("https://github.com/myorg/repo/issues/{}", title.len()),
+            }),
+            ..Default::default()
+        };
+        let mut issues = vec![
+            child(9, None),
+            child(7, Some("Onboarding")),
+            child(3, None),
+            child(5, Some("Billing")),
+            child(4, Some("Onboarding")),
+        ];
+
+        sort_issues(&mut issues, SortKey::Parent);
+        let numbers: Vec<u64> = issues.iter().map(|i| i.number).collect();
+        assert_eq!(numbers, vec![5, 4, 7, 3, 9]);
+    }
+
+    #[test]
+    fn test_show_comments_and_sort() {
+        let mut issues = sample_issues();
+        issues[0].comment_count = 1;
+        issues[1].comment_count = 12;
+
+        sort_issues(&mut issues, SortKey::Comments);
+        let numbers: Vec<u64> = issues.iter().map(|i| i.number).collect();
+        assert_eq!(numbers, vec![45, 42]);
+
+        let options = OutputOptions {
+            show_comments: true,
+            ..OutputOptions::default()
+        };
+        let output = format_issues(&issues, &options);
+        assert!(output.contains("Add dark mode support (12 comments)\n"));
+        assert!(output.contains("Fix login button alignment (1 comment)\n"));
+    }
+
+    #[test]
+    fn test_show_age_and_stats() {
+        let mut issues = sample_issues();
+        issues[0].created_at = Some(Utc.with_ymd_and_hms(2024, 1, 12, 14, 30, 0).unwrap());
+        issues[1].created_at = Some(Utc.with_ymd_and_hms(2024, 1, 15, 11, 0, 0).unwrap());
+        let opts = OutputOptions {
+            show_age: true,
+            show_stats: true,
+            ..OutputOptions::default()
+        };
+
+        let output = format_issues(&issues, &opts);
+        assert!(output.starts_with("Found 2 issue(s):\nTime to close: median 1d, max 3d\n\n"));
+        assert!(output.contains("Fix login button alignment (open 3d)\n"));
+        assert!(output.contains("Add dark mode support (open 5h)\n"));
+
+        // Issues without a creation date are left unannotated
+        issues[1].created_at = None;
+        let output = format_issues(&issues, &opts);
+        assert!(output.contains("Time to close: median 3d, max 3d"));
+        assert!(output.contains("Add dark mode support\n"));
+    }
+
+    #[test]
+    fn test_merged_issues_listed() {
+        let mut issues = sample_issues();
+        issues[0].merged.push(MergedIssue {
+            number: 7,
+            url: "https://github.com/myorg/api/issues/7".to_string(),
+            repository: "myorg/api".to_string(),
+        });
+
+        let text = format_issues(&issues, &OutputOptions::default());
+        assert!(text.contains("Fix login button alignment (also myorg/api#7)\n"));
+
+        let markdown = format_issues(&issues, &options(OutputFormat::Markdown, GroupBy::None));
+        assert!(markdown.contains("(also [myorg/api#7](https://github.com/myorg/api/issues/7))"));
+
+        let urls = format_issues(&issues, &options(OutputFormat::Urls, GroupBy::None));
+        assert_eq!(
+            urls,
+            "https://github.com/myorg/repo/issues/42\n\
+             https://github.com/myorg/api/issues/7\n\
+             https://github.com/myorg/repo/issues/45"
+        );
+    }
+
+    #[test]
+    fn test_no_header() {
+        let opts = OutputOptions {
+            no_header: true,
+            ..options(OutputFormat::Markdown, GroupBy::None)
+        };
+        let output = format_issues(&sample_issues(), &opts);
+        assert!(output.starts_with("- **[myorg/repo#42]"));
+
+        let opts = OutputOptions {
+            no_header: true,
+            ..OutputOptions::default()
+        };
+        let output = format_issues(&sample_issues(), &opts);
+        assert!(output.starts_with("• [myorg/repo#42] Fix login button alignment\n"));
+    }
+
+    #[test]
+    fn test_link_footnotes() {
+        let opts = OutputOptions {
+            link_footnotes: true,
+            ..options(OutputFormat::Markdown, GroupBy::Parent)
+        };
+        let output = format_issues(&sample_issues(), &opts);
+        assert!(output.contains("### [UI Improvements][1]\n"));
+        assert!(output.contains("- [myorg/repo#45][2]: Add dark mode support\n"));
+        assert!(output.ends_with("[1]: https://github.com/myorg/repo/issues/40\n\
+             [2]: https://github.com/myorg/repo/issues/45\n\
+             [3]: https://github.com/myorg/repo/issues/42"));
+    }
+
+    #[test]
+    fn test_reference_links_reuses_numbers() {
+        let markdown = "[a](https://x.test/1) and [b](https://x.test/1), then [c](https://x.test/2)";
+        assert_eq!(
+            reference_links(markdown),
+            "[a][1] and [b][1], then [c][2]\n\n[1]: https://x.test/1\n[2]: https://x.test/2"
+        );
+        assert_eq!(reference_links("no links"), "no links");
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(Duration::days(3) + Duration::hours(20)), "3d");
+        assert_eq!(format_age(Duration::hours(5)), "5h");
+        assert_eq!(format_age(Duration::minutes(12)), "12m");
+    }
+
+    #[test]
+    fn test_list_org() {
+        let output = format_issues(&sample_issues(), &options(OutputFormat::Org, GroupBy::None));
+        let expected = "* Summary (2 issues)\n\n\
+            ** [[https://github.com/myorg/repo/issues/42][myorg/repo#42]] Fix login button alignment\n\
+            :PROPERTIES:\n\
+            :CLOSED: [2024-01-15 Mon 14:30]\n\
+            :END:\n\
+            ** [[https://github.com/myorg/repo/issues/45][myorg/repo#45]] Add dark mode support\n\
+            :PROPERTIES:\n\
+            :CLOSED: [2024-01-15 Mon 16:00]\n\
+            :PARENT: [[https://github.com/myorg/repo/issues/40][UI Improvements]]\n\
+            :END:";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_grouped_org() {
+        let output = format_issues(&sample_issues(), &options(OutputFormat::Org, GroupBy::Parent));
+        let expected = "* Summary (2 issues)\n\n\
+            ** [[https://github.com/myorg/repo/issues/40][UI Improvements]]\n\
+            *** [[https://github.com/myorg/repo/issues/45][myorg/repo#45]] Add dark mode support\n\
+            :PROPERTIES:\n\
+            :CLOSED: [2024-01-15 Mon 16:00]\n\
+            :END:\n\
+            ** Standalone Issues\n\
+            *** [[https://github.com/myorg/repo/issues/42][myorg/repo#42]] Fix login button alignment\n\
+            :PROPERTIES:\n\
+            :CLOSED: [2024-01-15 Mon 14:30]\n\
+            :END:";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_list_urls() {
+        let mut issues = sample_issues();
+        issues.push(Issue {
+            title: "Draft idea".to_string(),
+            is_draft: true,
+            ..Default::default()
+        });
+        let output = format_issues(&issues, &options(OutputFormat::Urls, GroupBy::None));
+        assert_eq!(
+            output,
+            "https://github.com/myorg/repo/issues/42\nhttps://github.com/myorg/repo/issues/45"
+        );
+    }
+
+    #[test]
+    fn test_grouped_urls() {
+        let output = format_issues(&sample_issues(), &options(OutputFormat::Urls, GroupBy::Parent));
+        let expected = "https://github.com/myorg/repo/issues/40\n\
+            https://github.com/myorg/repo/issues/45\n\n\
+            https://github.com/myorg/repo/issues/42";
+        assert_eq!(output, expected);
+
+        let parents = OutputOptions {
+            parent_only: true,
+            ..options(OutputFormat::Urls, GroupBy::None)
+        };
+        assert_eq!(format_issues(&sample_issues(), &parents), "https://github.com/myorg/repo/issues/40");
+    }
+
+    fn multi_repo_issues() -> Vec<Issue> {
+        let mut issues = sample_issues();
+        issues.push(Issue {
+            number: 7,
+            title: "Update API docs".to_string(),
+            url: "https://github.com/myorg/docs/issues/7".to_string(),
+            repository: "myorg/docs".to_string(),
+            ..Default::default()
+        });
+        issues.reverse();
+        issues
+    }
+
+    #[test]
+    fn test_group_by_repository_text() {
+        let output = format_issues(&multi_repo_issues(), &options(OutputFormat::Text, GroupBy::Repository));
+        let expected = "Found 3 issue(s):\n\n\
+            ▶ myorg/docs (1)\n  \
+            • #7 Update API docs\n    \
+            https://github.com/myorg/docs/issues/7\n\n\
+            ▶ myorg/repo (2)\n  \
+            • #42 Fix login button alignment\n    \
+            https://github.com/myorg/repo/issues/42\n  \
+            • #45 Add dark mode support\n    \
+            https://github.com/myorg/repo/issues/45";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_group_by_repository_markdown() {
+        let output = format_issues(&multi_repo_issues(), &options(OutputFormat::Markdown, GroupBy::Repository));
+        let expected = "## Summary (3 issues)\n\n\
+            ### myorg/docs (1)\n\n\
+            - [#7](https://github.com/myorg/docs/issues/7): Update API docs\n\n\
+            ### myorg/repo (2)\n\n\
+            - [#42](https://github.com/myorg/repo/issues/42): Fix login button alignment\n\
+            - [#45](https://github.com/myorg/repo/issues/45): Add dark mode support";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_group_by_milestone() {
+        let mut issues = multi_repo_issues();
+        issues[0].milestone = Some("v2.0".to_string());
+        issues[2].milestone = Some("v2.0".to_string());
+
+        let output = format_issues(&issues, &options(OutputFormat::Markdown, GroupBy::Milestone));
+        let expected = "## Summary (3 issues)\n\n\
+            ### v2.0 (2)\n\n\
+            - [#7](https://github.com/myorg/docs/issues/7): Update API docs\n\
+            - [#42](https://github.com/myorg/repo/issues/42): Fix login button alignment\n\n\
+            ### No Milestone (1)\n\n\
+            - [#45](https://github.com/myorg/repo/issues/45): Add dark mode support";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_group_by_field() {
+        let mut issues = multi_repo_issues();
+        issues[0].fields.insert("Area".to_string(), "Docs".to_string());
+        issues[1].fields.insert("Area".to_string(), "Frontend".to_string());
+
+        let group_by: GroupBy = "field:Area".parse().unwrap();
+        let output = format_issues(&issues, &options(OutputFormat::Markdown, group_by));
+        let expected = "## Summary (3 issues)\n\n\
+            ### Docs (1)\n\n\
+            - [#7](https://github.com/myorg/docs/issues/7): Update API docs\n\n\
+            ### Frontend (1)\n\n\
+            - [#45](https://github.com/myorg/repo/issues/45): Add dark mode support\n\n\
+            ### Unspecified (1)\n\n\
+            - [#42](https://github.com/myorg/repo/issues/42): Fix login button alignment";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_parse_group_by() {
+        assert_eq!("milestone".parse::<GroupBy>().unwrap(), GroupBy::Milestone);
+        assert_eq!(
+            "field: Team".parse::<GroupBy>().unwrap(),
+            GroupBy::Field("Team".to_string())
+        );
+        assert!("field:".parse::<GroupBy>().is_err());
+        assert!("assignee".parse::<GroupBy>().is_err());
+    }
+
+    #[test]
+    fn test_append_separator() {
+        let now = Local.with_ymd_and_hms(2024, 10, 15, 9, 5, 0).unwrap();
+        assert_eq!(append_separator(now, &[]), "## 2024-10-15 09:05");
+        assert_eq!(
+            append_separator(now, &["column: Done".to_string(), "since: 7d".to_string()]),
+            "## 2024-10-15 09:05 (column: Done, since: 7d)"
+        );
+    }
+
+    #[test]
+    fn test_write_report_append() {
+        let path = std::env::temp_dir().join(format!("doner-append-{}.md", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        write_report(&path, "first", Some("## Monday")).unwrap();
+        write_report(&path, "second", Some("## Tuesday")).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "## Monday\n\nfirst\n\n## Tuesday\n\nsecond\n"
+        );
+
+        // Without a separator the file is overwritten
+        write_report(&path, "fresh", None).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "fresh\n");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_plain_report_has_no_escape_codes() {
+        let options = OutputOptions {
+            color: true,
+            hyperlinks: true,
+            ..OutputOptions::default()
+        };
+        assert!(format_issues(&sample_issues(), &options).contains('\x1b'));
+
+        let path = std::env::temp_dir().join(format!("doner-plain-{}.txt", std::process::id()));
+        write_report(&path, &format_issues(&sample_issues(), &options.plain()), None).unwrap();
+        assert!(!std::fs::read_to_string(&path).unwrap().contains('\x1b'));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_drafts_section() {
+        let mut issues = sample_issues();
+        issues.truncate(1);
+        issues.push(Issue {
+            id: "DI_1".to_string(),
+            title: "Rough idea".to_string(),
+            is_draft: true,
+            ..Default::default()
+        });
+
+        let output = format_issues(&issues, &options(OutputFormat::Markdown, GroupBy::None));
+        let expected = "## Summary (1 issues)\n\n\
+            - **[myorg/repo#42](https://github.com/myorg/repo/issues/42)**: Fix login button alignment\n  \
+            - Closed: 2024-01-15 14:30\n\n\
+            ### Drafts\n\n\
+            - Rough idea";
+        assert_eq!(output, expected);
+
+        let output = format_issues(&issues, &options(OutputFormat::Text, GroupBy::Parent));
+        assert!(output.ends_with("https://github.com/myorg/repo/issues/42\n\n▶ Drafts\n  • Rough idea"));
+    }
+
+    #[test]
+    fn test_issue_count_matches_header() {
+        let mut issues = sample_issues();
+        issues.push(Issue {
+            id: "DI_1".to_string(),
+            title: "Rough idea".to_string(),
+            is_draft: true,
+            ..Default::default()
+        });
+        assert_eq!(issue_count(&issues), 2);
+        let output = format_issues(&issues, &options(OutputFormat::Text, GroupBy::None));
+        assert!(output.starts_with("Found 2 issue(s):"), "{}", output);
+
+        // A board with only drafts has nothing to count
+        issues.retain(|issue| issue.is_draft);
+        assert_eq!(issue_count(&issues), 0);
+        let output = format_issues(&issues, &options(OutputFormat::Text, GroupBy::None));
+        assert!(output.starts_with("Found 0 issue(s):"), "{}", output);
+    }
+
+    #[test]
+    fn test_truncate_graphemes() {
+        assert_eq!(truncate_graphemes("Short", 10), None);
+        assert_eq!(truncate_graphemes("Exactly10!", 10), None);
+        assert_eq!(truncate_graphemes("Fix login button", 8).as_deref(), Some("Fix log…"));
+        assert_eq!(truncate_graphemes("Fix the bug", 5).as_deref(), Some("Fix…"));
+        // Multi-codepoint graphemes are kept whole
+        assert_eq!(truncate_graphemes("naïve 👩‍💻 café", 8).as_deref(), Some("naïve 👩‍💻…"));
+    }
+
+    #[test]
+    fn test_max_title_width_text_only() {
+        let mut opts = OutputOptions {
+            max_title_width: Some(10),
+            ..OutputOptions::default()
+        };
+        let output = format_issues(&sample_issues(), &opts);
+        assert!(output.contains("[myorg/repo#42] Fix login…\n"));
+
+        opts.format = OutputFormat::Markdown;
+        let output = format_issues(&sample_issues(), &opts);
+        assert!(output.contains("Fix login button alignment"));
+    }
+
+    #[test]
+    fn test_format_table() {
+        let output = format_issues(&sample_issues(), &options(OutputFormat::Table, GroupBy::None));
+        let rule = ["─".repeat(10), "─".repeat(3), "─".repeat(26), "─".repeat(10), "─".repeat(15)].join("  ");
+        let expected = format!(
+            "Found 2 issue(s):\n\n\
+             Repo{}#{}Title{}Closed{}Parent\n{}\n\
+             myorg/repo  #42  Fix login button alignment  2024-01-15  -\n\
+             myorg/repo  #45  Add dark mode support{}2024-01-15  UI Improvements",
+            " ".repeat(8),
+            " ".repeat(4),
+            " ".repeat(23),
+            " ".repeat(6),
+            rule,
+            " ".repeat(7)
+        );
+        assert_eq!(output, expected);
+
+        // Grouping doesn't apply; the Parent column already shows it
+        let grouped = format_issues(&sample_issues(), &options(OutputFormat::Table, GroupBy::Parent));
+        assert_eq!(grouped, expected);
+    }
+
+    #[test]
+    fn test_format_table_truncates_titles() {
+        let opts = OutputOptions {
+            max_title_width: Some(10),
+            no_header: true,
+            ..options(OutputFormat::Table, GroupBy::None)
+        };
+        let output = format_issues(&sample_issues(), &opts);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[2], "myorg/repo  #42  Fix login…  2024-01-15  -");
+        assert_eq!(lines[3], "myorg/repo  #45  Add dark…   2024-01-15  UI Improvements");
+    }
+
+    #[test]
+    fn test_locale_formatting() {
+        let locale = crate::locale::parse_locale("de-DE").unwrap();
+        let opts = OutputOptions {
+            date_format: locale.date_format.to_string(),
+            thousands_separator: Some(locale.thousands_separator),
+            ..OutputOptions::default()
+        };
+        let output = format_issues(&sample_issues(), &opts);
+        assert!(output.contains("Closed: 15.01.2024 14:30"), "{}", output);
+
+        let many: Vec<Issue> = (0..1200).map(|_| sample_issues().remove(0)).collect();
+        let markdown = OutputOptions {
+            format: OutputFormat::Markdown,
+            ..opts
+        };
+        assert!(format_issues(&many, &markdown).starts_with("## Summary (1.200 issues)"));
+    }
+
+    #[test]
+    fn test_compact_date_format() {
+        let locale = crate::locale::parse_locale("de-DE").unwrap();
+        let compact = OutputOptions {
+            compact: true,
+            no_header: true,
+            date_format: locale.date_format.to_string(),
+            ..OutputOptions::default()
+        };
+        assert!(format_issues(&sample_issues(), &compact).contains("— myorg/repo (15.01.2024)"));
+
+        let custom = OutputOptions {
+            date_format: "%b %-d, %Y".to_string(),
+            ..compact
+        };
+        assert!(format_issues(&sample_issues(), &custom).contains("— myorg/repo (Jan 15, 2024)"));
+    }
+
+    #[test]
+    fn test_table_date_format() {
+        let locale = crate::locale::parse_locale("de-DE").unwrap();
+        let table = OutputOptions {
+            format: OutputFormat::Table,
+            no_header: true,
+            date_format: locale.date_format.to_string(),
+            ..OutputOptions::default()
+        };
+        let output = format_issues(&sample_issues(), &table);
+        assert!(output.contains("  15.01.2024  "), "{}", output);
+        assert!(!output.contains("14:30"));
+
+        let custom = OutputOptions {
+            date_format: "%m/%d/%Y %-I:%M %p".to_string(),
+            ..table
+        };
+        assert!(format_issues(&sample_issues(), &custom).contains("  01/15/2024  "));
+    }
+
+    #[test]
+    fn test_date_only_format() {
+        assert_eq!(date_only_format(DEFAULT_DATE_FORMAT), "%Y-%m-%d");
+        assert_eq!(date_only_format("%m/%d/%Y %-I:%M %p"), "%m/%d/%Y");
+        assert_eq!(date_only_format("%Y-%m-%dT%H:%M"), "%Y-%m-%d");
+        assert_eq!(date_only_format("%d.%m.%Y"), "%d.%m.%Y");
+        assert_eq!(date_only_format("%H:%M %d/%m"), "%H:%M %d/%m");
+    }
+
+    #[test]
+    fn test_validate_date_format() {
+        assert!(validate_date_format("%d.%m.%Y").is_ok());
+        assert!(validate_date_format("%Q").is_err());
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("UI Improvements"), "ui-improvements");
+        assert_eq!(slugify("../../etc/passwd"), "etc-passwd");
+        assert_eq!(slugify("  Q3: Auth & SSO!  "), "q3-auth-sso");
+        assert_eq!(slugify("🚀"), "untitled");
+    }
+
+    #[test]
+    fn test_write_parent_files() {
+        let dir = std::env::temp_dir().join(format!("doner-parents-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let opts = options(OutputFormat::Markdown, GroupBy::Parent);
+
+        let written = write_parent_files(&dir, &sample_issues(), &opts).unwrap();
+        assert_eq!(written, vec![dir.join("ui-improvements.md"), dir.join("standalone.md")]);
+
+        let epic = std::fs::read_to_string(dir.join("ui-improvements.md")).unwrap();
+        assert!(epic.starts_with("# UI Improvements\n\n## Summary (1 issues)"));
+        assert!(epic.contains("Add dark mode support"));
+        let standalone = std::fs::read_to_string(dir.join("standalone.md")).unwrap();
+        assert!(standalone.contains("Fix login button alignment"));
+
+        // The default text format writes markdown files
+        let written = write_parent_files(&dir, &sample_issues(), &options(OutputFormat::Text, GroupBy::Parent)).unwrap();
+        assert_eq!(written, vec![dir.join("ui-improvements.md"), dir.join("standalone.md")]);
+        let epic = std::fs::read_to_string(dir.join("ui-improvements.md")).unwrap();
+        assert!(epic.starts_with("# UI Improvements\n\n## Summary (1 issues)"), "{}", epic);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_custom_date_format() {
+        let opts = OutputOptions {
+            date_format: "%d/%m/%Y".to_string(),
+            ..OutputOptions::default()
+        };
+        let output = format_issues(&sample_issues(), &opts);
+        assert!(output.contains("Closed: 15/01/2024"));
+    }
+}
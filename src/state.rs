@@ -0,0 +1,160 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const STATE_FILE: &str = "state.json";
+
+/// Persistent state carried between runs
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    /// Project identifier used by the last successful summarize run
+    #[serde(default)]
+    pub last_project: Option<String>,
+    /// Last validated login per GitHub host, so `auth status` can skip the network
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub logins: BTreeMap<String, CachedLogin>,
+}
+
+/// A login and the hash of the token it was validated with (never the token itself)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedLogin {
+    pub token_sha256: String,
+    pub login: String,
+}
+
+impl State {
+    /// Cached login for `host`, if it was validated with the token hashing to `token_sha256`
+    pub fn cached_login(&self, host: &str, token_sha256: &str) -> Option<&str> {
+        self.logins
+            .get(host)
+            .filter(|cached| cached.token_sha256 == token_sha256)
+            .map(|cached| cached.login.as_str())
+    }
+}
+
+/// Get the state directory
+/// Priority: $XDG_STATE_HOME/doner > ~/.local/state/doner
+pub fn state_dir() -> Result<PathBuf> {
+    if let Ok(dir) = std::env::var("XDG_STATE_HOME")
+        && !dir.is_empty()
+    {
+        return Ok(PathBuf::from(dir).join("doner"));
+    }
+
+    let home = std::env::var("HOME").map_err(|_| anyhow!("Could not determine home directory"))?;
+    Ok(PathBuf::from(home).join(".local").join("state").join("doner"))
+}
+
+/// Load the state file, returning defaults if it doesn't exist yet
+pub fn load() -> Result<State> {
+    let path = state_dir()?.join(STATE_FILE);
+    if !path.exists() {
+        return Ok(State::default());
+    }
+
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read state file {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse state file {}", path.display()))
+}
+
+/// Write the state file, creating the state directory if needed
+pub fn save(state: &State) -> Result<()> {
+    let dir = state_dir()?;
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create state directory {}", dir.display()))?;
+
+    let path = dir.join(STATE_FILE);
+    let contents = serde_json::to_string_pretty(state)?;
+    std::fs::write(&path, contents)
+        .with_context(|| format!("Failed to write state file {}", path.display()))
+}
+
+/// Resolve the project to use, falling back to the last-used project
+pub fn resolve_project(project_id: Option<String>) -> Result<String> {
+    if let Some(project_id) = project_id {
+        return Ok(project_id);
+    }
+
+    load()?.last_project.ok_or_else(|| {
+        anyhow!(
+            "No project specified and no previous project found.\n  \
+             Pass a project ID, e.g. 'doner summarize myorg/5'"
+        )
+    })
+}
+
+/// Remember the project used by a successful run
+pub fn save_last_project(project_id: &str) -> Result<()> {
+    let mut state = load()?;
+    state.last_project = Some(project_id.to_string());
+    save(&state)
+}
+
+/// Read the RFC 3339 timestamp stored by a previous `--since-file` run, if the file exists
+pub fn read_since_file(path: &Path) -> Result<Option<DateTime<Utc>>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read since file {}", path.display()))?;
+    DateTime::parse_from_rfc3339(contents.trim())
+        .map(|t| Some(t.with_timezone(&Utc)))
+        .with_context(|| format!("Since file {} does not contain an RFC 3339 timestamp", path.display()))
+}
+
+/// Record the start of a successful run for the next `--since-file` run
+pub fn write_since_file(path: &Path, time: DateTime<Utc>) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).with_context(|| format!("Failed to create directory {}", dir.display()))?;
+    }
+    std::fs::write(path, format!("{}\n", time.to_rfc3339_opts(SecondsFormat::Secs, true)))
+        .with_context(|| format!("Failed to write since file {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_cached_login() {
+        let mut state = State::default();
+        state.logins.insert(
+            "github.com".to_string(),
+            CachedLogin {
+                token_sha256: "abc".to_string(),
+                login: "octocat".to_string(),
+            },
+        );
+
+        assert_eq!(state.cached_login("github.com", "abc"), Some("octocat"));
+        // A different token (e.g. after re-login) must be validated again
+        assert_eq!(state.cached_login("github.com", "def"), None);
+        assert_eq!(state.cached_login("ghe.corp.com", "abc"), None);
+
+        let json = serde_json::to_string(&state).unwrap();
+        let parsed: State = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.logins, state.logins);
+        let legacy: State = serde_json::from_str(r#"{"last_project":"myorg/5"}"#).unwrap();
+        assert!(legacy.logins.is_empty());
+    }
+
+    #[test]
+    fn test_since_file_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("doner-since-{}", std::process::id()));
+        let path = dir.join("daily.since");
+        assert_eq!(read_since_file(&path).unwrap(), None);
+
+        let time = Utc.with_ymd_and_hms(2024, 10, 15, 9, 30, 0).unwrap();
+        write_since_file(&path, time).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "2024-10-15T09:30:00Z\n");
+        assert_eq!(read_since_file(&path).unwrap(), Some(time));
+
+        std::fs::write(&path, "last tuesday").unwrap();
+        assert!(read_since_file(&path).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,156 @@
+use anyhow::{anyhow, Context, Result};
+use minijinja::{context, Environment};
+
+use crate::models::Issue;
+
+/// Built-in themes embedded in the binary, in display order
+const THEMES: &[(&str, &str)] = &[
+    ("changelog", include_str!("templates/changelog.md.j2")),
+    ("digest", include_str!("templates/digest.md.j2")),
+    ("standup", include_str!("templates/standup.md.j2")),
+];
+
+/// Names of all built-in themes
+pub fn theme_names() -> Vec<&'static str> {
+    THEMES.iter().map(|(name, _)| *name).collect()
+}
+
+/// Look up the template source of a built-in theme
+pub fn builtin_theme(name: &str) -> Result<&'static str> {
+    THEMES
+        .iter()
+        .find(|(theme, _)| theme.eq_ignore_ascii_case(name))
+        .map(|(_, source)| *source)
+        .ok_or_else(|| {
+            anyhow!(
+                "Unknown theme '{}'. Available themes: {}",
+                name,
+                theme_names().join(", ")
+            )
+        })
+}
+
+/// Load a custom template from disk
+pub fn load_template_file(path: &str) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("Failed to read template file '{}'", path))
+}
+
+/// Variables always present in the template context, which --template-var can't override
+const BUILTIN_VARIABLES: &[&str] = &["issues", "count", "column"];
+
+/// Parse a `--template-var name=value` entry. Names must be identifiers usable in templates.
+pub fn parse_template_var(entry: &str) -> Result<(String, String)> {
+    let (name, value) = entry
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Invalid --template-var '{}': expected name=value", entry))?;
+    let name = name.trim();
+
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(anyhow!(
+            "Invalid --template-var name '{}': use letters, digits and underscores, not starting with a digit",
+            name
+        ));
+    }
+    if BUILTIN_VARIABLES.contains(&name) {
+        return Err(anyhow!("--template-var cannot override the built-in '{}' variable", name));
+    }
+
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// Render a template with the fetched issues
+///
+/// The template context exposes:
+/// - `issues` - the list of issues
+/// - `count` - the number of issues
+/// - `column` - the column the issues were fetched from
+/// - every `vars` entry by name (from --template-var)
+pub fn render(source: &str, issues: &[Issue], column: &str, vars: &[(String, String)]) -> Result<String> {
+    let mut env = Environment::new();
+    env.add_template("report", source)
+        .context("Failed to parse template")?;
+
+    let template = env.get_template("report")?;
+    let output = template
+        .render(context! {
+            issues => issues,
+            count => issues.len(),
+            column => column,
+            ..minijinja::Value::from_iter(vars.iter().cloned())
+        })
+        .context("Failed to render template")?;
+
+    Ok(output.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(number: u64, title: &str) -> Issue {
+        Issue {
+            number,
+            title: title.to_string(),
+            url: format!("https://github.com/myorg/repo/issues/{}", number),
+            closed_at: None,
+            parent: None,
+            repository: "myorg/repo".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_all_themes_render() {
+        let issues = vec![issue(1, "First"), issue(2, "Second")];
+        for name in theme_names() {
+            let source = builtin_theme(name).unwrap();
+            let output = render(source, &issues, "Done", &[]).unwrap();
+            assert!(output.contains("First"), "theme {} missing issue", name);
+        }
+    }
+
+    #[test]
+    fn test_changelog_theme() {
+        let output = render(builtin_theme("changelog").unwrap(), &[issue(7, "Fix bug")], "Done", &[]).unwrap();
+        assert_eq!(
+            output,
+            "## Changelog\n\n- Fix bug ([myorg/repo#7](https://github.com/myorg/repo/issues/7))"
+        );
+    }
+
+    #[test]
+    fn test_unknown_theme() {
+        assert!(builtin_theme("nope").is_err());
+    }
+
+    #[test]
+    fn test_custom_template_context() {
+        let output = render("{{ count }} in {{ column }}", &[issue(1, "A")], "Review", &[]).unwrap();
+        assert_eq!(output, "1 in Review");
+    }
+
+    #[test]
+    fn test_template_vars() {
+        let vars = vec![
+            parse_template_var("title=Sprint 13 report").unwrap(),
+            parse_template_var("period=").unwrap(),
+        ];
+        let output = render("# {{ title }} ({{ count }}){{ period }}", &[issue(1, "A")], "Done", &vars).unwrap();
+        assert_eq!(output, "# Sprint 13 report (1)");
+
+        // Only the first '=' separates the name
+        assert_eq!(parse_template_var("q=a=b").unwrap(), ("q".to_string(), "a=b".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_template_vars() {
+        assert!(parse_template_var("title").unwrap_err().to_string().contains("expected name=value"));
+        assert!(parse_template_var("=value").is_err());
+        assert!(parse_template_var("1st=value").is_err());
+        assert!(parse_template_var("my-title=value").is_err());
+        assert!(parse_template_var("count=3").unwrap_err().to_string().contains("built-in 'count'"));
+    }
+}
@@ -1,5 +1,5 @@
 use anyhow::{anyhow, Result};
-use chrono::{DateTime, Datelike, Duration, Local, NaiveTime, TimeZone, Utc};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
 
 /// Parse a time filter string and return a DateTime<Utc>
 ///
@@ -11,68 +11,77 @@ use chrono::{DateTime, Datelike, Duration, Local, NaiveTime, TimeZone, Utc};
 /// - "today" - start of today
 /// - "this-week" - start of current week (Monday)
 /// - "this-month" - start of current month
+/// - "friday" - start of the most recent past Friday (a week ago if today is Friday)
+/// - "last-friday" - start of the Friday before that
+/// - "2024-10-15" - start of that day
+/// - "2024-10-15T09:30:00Z" - an exact RFC 3339 timestamp
 pub fn parse_time_filter(input: &str) -> Result<DateTime<Utc>> {
+    parse_time_filter_at(input, Local::now())
+}
+
+/// Parse a time filter relative to `now`; keywords resolve to midnight in `now`'s timezone
+fn parse_time_filter_at<Tz: TimeZone>(input: &str, now: DateTime<Tz>) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(input.trim()) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
     let input = input.trim().to_lowercase();
+    let today = now.date_naive();
 
     // Handle special keywords
-    match input.as_str() {
-        "yesterday" => {
-            let yesterday = Local::now().date_naive() - Duration::days(1);
-            let start_of_day = yesterday.and_time(NaiveTime::MIN);
-            return Ok(Local
-                .from_local_datetime(&start_of_day)
-                .single()
-                .ok_or_else(|| anyhow!("Invalid date/time"))?
-                .with_timezone(&Utc));
-        }
-        "today" => {
-            let today = Local::now().date_naive();
-            let start_of_day = today.and_time(NaiveTime::MIN);
-            return Ok(Local
-                .from_local_datetime(&start_of_day)
-                .single()
-                .ok_or_else(|| anyhow!("Invalid date/time"))?
-                .with_timezone(&Utc));
-        }
-        "this-week" => {
-            let now = Local::now();
-            let days_since_monday = now.weekday().num_days_from_monday() as i64;
-            let monday = now.date_naive() - Duration::days(days_since_monday);
-            let start_of_day = monday.and_time(NaiveTime::MIN);
-            return Ok(Local
-                .from_local_datetime(&start_of_day)
-                .single()
-                .ok_or_else(|| anyhow!("Invalid date/time"))?
-                .with_timezone(&Utc));
-        }
-        "this-month" => {
-            let now = Local::now();
-            let first_of_month = now
-                .date_naive()
-                .with_day(1)
-                .ok_or_else(|| anyhow!("Invalid date"))?;
-            let start_of_day = first_of_month.and_time(NaiveTime::MIN);
-            return Ok(Local
-                .from_local_datetime(&start_of_day)
-                .single()
-                .ok_or_else(|| anyhow!("Invalid date/time"))?
-                .with_timezone(&Utc));
-        }
-        _ => {}
+    let day = match input.as_str() {
+        "yesterday" => Some(today - Duration::days(1)),
+        "today" => Some(today),
+        "this-week" => Some(today - Duration::days(now.weekday().num_days_from_monday() as i64)),
+        "this-month" => Some(today.with_day(1).ok_or_else(|| anyhow!("Invalid date"))?),
+        other if let Ok(day) = NaiveDate::parse_from_str(other, "%Y-%m-%d") => Some(day),
+        other => parse_weekday(other).map(|(weekday, weeks_back)| {
+            let days_back = match (now.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7 {
+                // Strictly in the past: the same weekday means a week ago
+                0 => 7,
+                days => days as i64,
+            };
+            today - Duration::days(days_back) - Duration::weeks(weeks_back)
+        }),
+    };
+    if let Some(day) = day {
+        return Ok(now
+            .timezone()
+            .from_local_datetime(&day.and_time(NaiveTime::MIN))
+            .single()
+            .ok_or_else(|| anyhow!("Invalid date/time"))?
+            .with_timezone(&Utc));
     }
 
     // Parse duration format (e.g., "7d", "24h", "30m")
     if let Some(duration) = parse_duration(&input) {
-        let now = Utc::now();
-        return Ok(now - duration);
+        return Ok(now.with_timezone(&Utc) - duration);
     }
 
     Err(anyhow!(
-        "Invalid time filter: '{}'. Use formats like: 7d, 24h, 30m, yesterday, today, this-week, this-month",
+        "Invalid time filter: '{}'. Use formats like: 7d, 24h, 30m, yesterday, today, this-week, this-month, friday, last-friday, 2024-10-15",
         input
     ))
 }
 
+/// Midnight at the start of `day` in the local timezone
+pub fn local_midnight(day: NaiveDate) -> Result<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&day.and_time(NaiveTime::MIN))
+        .earliest()
+        .map(|midnight| midnight.with_timezone(&Utc))
+        .ok_or_else(|| anyhow!("Invalid date/time"))
+}
+
+/// Parse "friday" or "last-friday", returning the weekday and how many extra weeks to go back
+fn parse_weekday(input: &str) -> Option<(Weekday, i64)> {
+    let (name, weeks_back) = match input.strip_prefix("last-") {
+        Some(name) => (name, 1),
+        None => (input, 0),
+    };
+    name.parse::<Weekday>().ok().map(|weekday| (weekday, weeks_back))
+}
+
 fn parse_duration(input: &str) -> Option<Duration> {
     let input = input.trim();
 
@@ -101,27 +110,98 @@ fn parse_duration(input: &str) -> Option<Duration> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::FixedOffset;
+
+    /// Wednesday 2024-10-16 15:30 UTC
+    fn reference_now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 10, 16, 15, 30, 0).unwrap()
+    }
 
     #[test]
     fn test_parse_days() {
-        let result = parse_time_filter("7d").unwrap();
-        let expected = Utc::now() - Duration::days(7);
-        // Allow 1 second tolerance
-        assert!((result - expected).num_seconds().abs() < 1);
+        let result = parse_time_filter_at("7d", reference_now()).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 9, 15, 30, 0).unwrap());
     }
 
     #[test]
     fn test_parse_hours() {
-        let result = parse_time_filter("24h").unwrap();
-        let expected = Utc::now() - Duration::hours(24);
-        assert!((result - expected).num_seconds().abs() < 1);
+        let result = parse_time_filter_at("24h", reference_now()).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 15, 15, 30, 0).unwrap());
     }
 
     #[test]
     fn test_parse_minutes() {
-        let result = parse_time_filter("30m").unwrap();
-        let expected = Utc::now() - Duration::minutes(30);
-        assert!((result - expected).num_seconds().abs() < 1);
+        let result = parse_time_filter_at("30m", reference_now()).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 16, 15, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_yesterday() {
+        let result = parse_time_filter_at("yesterday", reference_now()).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 15, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_this_week() {
+        let result = parse_time_filter_at("this-week", reference_now()).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 14, 0, 0, 0).unwrap());
+
+        // On a Monday the week starts today
+        let monday = Utc.with_ymd_and_hms(2024, 10, 14, 9, 0, 0).unwrap();
+        let result = parse_time_filter_at("this-week", monday).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 14, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_this_month() {
+        let result = parse_time_filter_at("this-month", reference_now()).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 1, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_weekday() {
+        // Reference is a Wednesday
+        let result = parse_time_filter_at("monday", reference_now()).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 14, 0, 0, 0).unwrap());
+
+        let result = parse_time_filter_at("Friday", reference_now()).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 11, 0, 0, 0).unwrap());
+
+        let result = parse_time_filter_at("last-friday", reference_now()).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 4, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_weekday_same_day_is_a_week_ago() {
+        let result = parse_time_filter_at("wednesday", reference_now()).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 9, 0, 0, 0).unwrap());
+
+        let result = parse_time_filter_at("last-wednesday", reference_now()).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 2, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_keywords_use_local_midnight() {
+        // 01:00 on Oct 16 at UTC+2 is still Oct 15 in UTC
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let now = offset.with_ymd_and_hms(2024, 10, 16, 1, 0, 0).unwrap();
+        let result = parse_time_filter_at("today", now).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 15, 22, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_rfc3339() {
+        let result = parse_time_filter("2024-10-15T11:30:00+02:00").unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 10, 15, 9, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_date() {
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let now = offset.with_ymd_and_hms(2024, 11, 20, 12, 0, 0).unwrap();
+        let result = parse_time_filter_at("2024-10-01", now).unwrap();
+        assert_eq!(result, Utc.with_ymd_and_hms(2024, 9, 30, 22, 0, 0).unwrap());
+        assert!(parse_time_filter_at("2024-13-01", now).is_err());
     }
 
     #[test]
@@ -0,0 +1,92 @@
+use anyhow::{anyhow, Context, Result};
+use minijinja::{context, Environment};
+use serde_json::{json, Value};
+
+/// Build the JSON body for a webhook. Without a template this is the Slack-compatible
+/// `{"text": ...}`; a template renders with `text` and must produce JSON, e.g.
+/// `{"content": {{ text | tojson }}}` for Discord
+pub fn render_payload(text: &str, template: Option<&str>) -> Result<Value> {
+    let Some(source) = template else {
+        return Ok(json!({ "text": text }));
+    };
+
+    let mut env = Environment::new();
+    env.add_template("payload", source)
+        .context("Failed to parse payload template")?;
+    let rendered = env
+        .get_template("payload")?
+        .render(context! { text => text })
+        .context("Failed to render payload template")?;
+
+    serde_json::from_str(&rendered).map_err(|e| {
+        anyhow!(
+            "Payload template did not produce valid JSON ({}). Use {{{{ text | tojson }}}} to insert the report",
+            e
+        )
+    })
+}
+
+/// POST the payload to an incoming webhook, returning the HTTP status on success
+pub async fn post(url: &str, payload: &Value) -> Result<reqwest::StatusCode> {
+    let response = crate::http::client()?
+        .post(url)
+        .header("User-Agent", "doner-cli")
+        .json(payload)
+        .send()
+        .await
+        .map_err(|e| crate::http::request_error(e, "Failed to post report to webhook"))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Webhook rejected the report (HTTP {}): {}", status, body.trim()));
+    }
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_render_payload() {
+        assert_eq!(render_payload("Done: 3", None).unwrap(), json!({ "text": "Done: 3" }));
+
+        let discord = render_payload("line \"one\"\nline two", Some(r#"{"content": {{ text | tojson }}}"#)).unwrap();
+        assert_eq!(discord, json!({ "content": "line \"one\"\nline two" }));
+
+        let err = render_payload("x", Some(r#"{"text": "{{ text }}"#)).unwrap_err();
+        assert!(err.to_string().contains("valid JSON"));
+    }
+
+    #[tokio::test]
+    async fn test_post() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(body_json(json!({ "text": "report" })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let status = post(&format!("{}/hook", server.uri()), &json!({ "text": "report" })).await.unwrap();
+        assert_eq!(status, 200);
+    }
+
+    #[tokio::test]
+    async fn test_post_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(400).set_body_string("invalid_payload"))
+            .mount(&server)
+            .await;
+
+        let err = post(&server.uri(), &json!({ "text": "report" })).await.unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("400"), "{}", message);
+        assert!(message.contains("invalid_payload"), "{}", message);
+    }
+}